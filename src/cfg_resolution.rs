@@ -0,0 +1,241 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, Expr, Field, Member, Meta, Stmt, Token};
+
+/// A member of a field, and the condition under which the field has it.
+pub struct CfgMember {
+    pub cfg: Option<Attribute>,
+    pub member: Member
+}
+
+/// The field's `#[cfg]`s, and its `#[cfg_attr]`s that only add `cfg`, `doc` or `deprecated`, which are meaningful on any
+/// item generated for the field.
+pub fn cfg_attrs(field: &Field) -> Vec<Attribute> {
+    field.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg") || is_item_cfg_attr(attr))
+        .cloned()
+        .collect()
+}
+
+fn is_item_cfg_attr(attr: &Attribute) -> bool {
+    if !attr.path().is_ident("cfg_attr") {
+        return false;
+    }
+
+    // The first meta is the condition, the rest are the attributes it adds
+    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .is_ok_and(|metas| metas.len() > 1 && metas
+            .iter()
+            .skip(1)
+            .all(|meta| ["cfg", "doc", "deprecated"].iter().any(|name| meta.path().is_ident(name))))
+}
+
+/// The condition of the field's `#[cfg]`s, if it has any.
+pub fn cfg_condition(field: &Field) -> Option<TokenStream> {
+    let predicates = field.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.parse_args::<TokenStream>().ok())
+        .collect::<Vec<TokenStream>>();
+
+    (!predicates.is_empty()).then(|| all(predicates))
+}
+
+/// Whether the fields' `#[cfg]` conditions can never hold together, because one requires `not(p)` of a predicate `p`
+/// the other requires. Fields without a condition are always compiled, so they're never disjoint from another field.
+pub fn cfg_disjoint(field: &Field, other: &Field) -> bool {
+    let (Some(condition), Some(other_condition)) = (cfg_condition(field), cfg_condition(other)) else {
+        return false;
+    };
+    let predicates = conjuncts(condition);
+    let other_predicates = conjuncts(other_condition);
+    let contradicts = |predicates: &[String], others: &[String]| predicates
+        .iter()
+        .any(|predicate| others.iter().any(|other| *other == format!("not ({})", predicate)));
+    contradicts(&predicates, &other_predicates) || contradicts(&other_predicates, &predicates)
+}
+
+/// The members of the field at `position` in `fields`. A field in a tuple struct has the index of the fields before it
+/// that exist, so fields with `#[cfg]`s before it give it a member for each number of their conditions that can hold.
+pub fn cfg_members(fields: &[&Field], position: usize) -> Vec<CfgMember> {
+    if let Some(ident) = &fields[position].ident {
+        return vec![CfgMember { cfg: None, member: Member::from(ident.clone()) }];
+    }
+
+    let preceding = &fields[..position];
+    let fixed = preceding.iter().filter(|field| cfg_condition(field).is_none()).count();
+    let conditions = preceding.iter().filter_map(|field| cfg_condition(field)).collect::<Vec<TokenStream>>();
+    if conditions.is_empty() {
+        return vec![CfgMember { cfg: None, member: Member::from(fixed) }];
+    }
+
+    let mut combinations = vec![vec![]; conditions.len() + 1];
+    for combination in 0..(1usize << conditions.len()) {
+        let predicates = conditions
+            .iter()
+            .enumerate()
+            .map(|(i, condition)| if combination & (1 << i) != 0 {
+                condition.clone()
+            } else {
+                quote! { not(#condition) }
+            })
+            .collect::<Vec<TokenStream>>();
+        combinations[combination.count_ones() as usize].push(all(predicates));
+    }
+
+    combinations
+        .into_iter()
+        .enumerate()
+        .map(|(held, predicates)| {
+            let predicate = any(predicates);
+            CfgMember {
+                cfg: Some(parse_quote! { #[cfg(#predicate)] }),
+                member: Member::from(fixed + held)
+            }
+        })
+        .collect()
+}
+
+/// Statements using a field's member, in a block for each of its members under that member's condition.
+pub fn cfg_stmts(members: &[CfgMember], f: impl Fn(&Member) -> Vec<Stmt>) -> Vec<Stmt> {
+    match members {
+        [CfgMember { cfg: None, member }] => f(member),
+        _ => members
+            .iter()
+            .map(|CfgMember { cfg, member }| {
+                let stmts = f(member);
+                parse_quote! {
+                    #cfg {
+                        #(#stmts)*
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// An expression using a field's member, evaluated for the member whose condition holds.
+pub fn cfg_expr(members: &[CfgMember], f: impl Fn(&Member) -> Expr) -> Expr {
+    match members {
+        [CfgMember { cfg: None, member }] => f(member),
+        _ => {
+            let stmts = members
+                .iter()
+                .map::<Stmt, _>(|CfgMember { cfg, member }| {
+                    let value = f(member);
+                    parse_quote! { #cfg let value = #value; }
+                });
+            parse_quote! {
+                {
+                    #(#stmts)*
+                    value
+                }
+            }
+        }
+    }
+}
+
+fn all(predicates: Vec<TokenStream>) -> TokenStream {
+    match predicates.as_slice() {
+        [predicate] => predicate.clone(),
+        _ => quote! { all(#(#predicates),*) }
+    }
+}
+
+/// The predicates that all have to hold for the condition to hold, as strings of their tokens.
+fn conjuncts(condition: TokenStream) -> Vec<String> {
+    match syn::parse2::<Meta>(condition.clone()) {
+        Ok(Meta::List(list)) if list.path.is_ident("all") => list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .map(|metas| metas.into_iter().flat_map(|meta| conjuncts(meta.to_token_stream())).collect())
+            .unwrap_or_else(|_| vec![condition.to_string()]),
+        _ => vec![condition.to_string()]
+    }
+}
+
+fn any(predicates: Vec<TokenStream>) -> TokenStream {
+    match predicates.as_slice() {
+        [predicate] => predicate.clone(),
+        _ => quote! { any(#(#predicates),*) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cfg_resolution::{cfg_attrs, cfg_condition, cfg_disjoint, cfg_members};
+    use quote::ToTokens;
+    use syn::{parse_quote, Field, ItemStruct};
+
+    #[test]
+    fn test_cfg_attrs() {
+        let field: Field = parse_quote! {
+            #[cfg(feature = "metrics")]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            #[cfg_attr(feature = "legacy", deprecated)]
+            #[serde(default)]
+            pub metrics: Metrics
+        };
+
+        let attrs = cfg_attrs(&field)
+            .iter()
+            .map(|attr| attr.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(attrs, vec![
+            "# [cfg (feature = \"metrics\")]",
+            "# [cfg_attr (feature = \"legacy\" , deprecated)]"
+        ]);
+    }
+
+    #[test]
+    fn test_cfg_condition() {
+        let field: Field = parse_quote! { #[cfg(unix)] #[cfg(feature = "metrics")] pub metrics: Metrics };
+        let plain: Field = parse_quote! { pub metrics: Metrics };
+
+        assert_eq!(cfg_condition(&field).unwrap().to_string(), "all (unix , feature = \"metrics\")");
+        assert!(cfg_condition(&plain).is_none());
+    }
+
+    #[test]
+    fn test_cfg_disjoint() {
+        let unix: Field = parse_quote! { #[cfg(unix)] pub foo: u8 };
+        let not_unix: Field = parse_quote! { #[cfg(all(feature = "metrics", not(unix)))] pub foo: u8 };
+        let metrics: Field = parse_quote! { #[cfg(feature = "metrics")] pub foo: u8 };
+        let plain: Field = parse_quote! { pub foo: u8 };
+
+        assert!(cfg_disjoint(&unix, &not_unix));
+        assert!(cfg_disjoint(&not_unix, &unix));
+        assert!(!cfg_disjoint(&unix, &metrics));
+        assert!(!cfg_disjoint(&metrics, &not_unix));
+        assert!(!cfg_disjoint(&unix, &plain));
+    }
+
+    #[test]
+    fn test_cfg_members() {
+        let item: ItemStruct = parse_quote! {
+            struct MyStruct(u8, #[cfg(a)] u8, u8, #[cfg(b)] u8, u8);
+        };
+        let fields = item.fields.iter().collect::<Vec<_>>();
+
+        let members = |position| cfg_members(&fields, position)
+            .iter()
+            .map(|member| (
+                member.cfg.to_token_stream().to_string(),
+                member.member.to_token_stream().to_string()
+            ))
+            .collect::<Vec<_>>();
+
+        assert_eq!(members(1), vec![(String::new(), "1".to_owned())]);
+        assert_eq!(members(2), vec![
+            ("# [cfg (not (a))]".to_owned(), "1".to_owned()),
+            ("# [cfg (a)]".to_owned(), "2".to_owned())
+        ]);
+        assert_eq!(members(4), vec![
+            ("# [cfg (all (not (a) , not (b)))]".to_owned(), "2".to_owned()),
+            ("# [cfg (any (all (a , not (b)) , all (not (a) , b)))]".to_owned(), "3".to_owned()),
+            ("# [cfg (all (a , b))]".to_owned(), "4".to_owned())
+        ]);
+    }
+}
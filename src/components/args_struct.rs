@@ -0,0 +1,141 @@
+use crate::cfg_resolution::cfg_attrs;
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Field, Fields, ItemStruct, Token};
+
+pub struct ArgsStruct<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ArgsStruct<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ArgsStruct<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            args,
+            options,
+            generics,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_decl,
+            where_clause,
+            ..
+        } = &generics;
+
+        if !options.clap || !matches!(self.fields, Fields::Named(_)) {
+            return;
+        }
+
+        let fields = args_fields(self.ctx);
+        let include_generics = fields.iter().any(|(_, field_ctx)| field_ctx.generic);
+
+        // Only doc comments, which clap uses as each argument's help, and `#[cfg]`s are kept
+        let punctuated_fields = fields
+            .into_iter()
+            .map(|(field, _)| {
+                let mut field = field.clone();
+                let cfg_attrs = cfg_attrs(&field);
+                field.attrs.retain(|attr| attr.path().is_ident("doc"));
+                field.attrs.extend(cfg_attrs);
+                field.attrs.push(parse_quote! { #[arg(long)] });
+                field.vis = parse_quote! { pub };
+                field
+            })
+            .collect::<Punctuated<Field, Token![,]>>();
+
+        let item_struct: ItemStruct = if include_generics {
+            parse_quote! {
+                #[derive(::clap::Args)]
+                pub struct #args #generics_decl #where_clause {
+                    #punctuated_fields
+                }
+            }
+        } else {
+            parse_quote! {
+                #[derive(::clap::Args)]
+                pub struct #args {
+                    #punctuated_fields
+                }
+            }
+        };
+
+        item_struct.to_tokens(tokens);
+    }
+}
+
+/// The fields taken as arguments, i.e. the params fields and the optional fields with setters.
+pub fn args_fields(ctx: &BuilderContext) -> Vec<(&Field, &FieldContext)> {
+    ctx.analysed_fields()
+        .filter(|(_, field_ctx)| field_ctx.required || field_ctx.options.has_setter())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ArgsStruct;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            /// A platypus.
+            #[builder(clap)]
+            #[derive(Debug)]
+            pub struct MyStruct<T> {
+                /// The platypus' name.
+                #[serde(rename = "platypusName")]
+                pub name: T,
+                /// The platypus' age.
+                age: Option<u8>,
+                #[builder(skip)]
+                pub cached: u64,
+                #[builder(setter(skip))]
+                pub nickname: Option<String>
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[derive(::clap::Args)]
+            pub struct MyStructArgs<T> {
+                /// The platypus' name.
+                #[arg(long)]
+                pub name: T,
+                /// The platypus' age.
+                #[arg(long)]
+                pub age: Option<u8>
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let args_struct = ArgsStruct::from(&ctx);
+
+        assert_eq!(
+            args_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_clap_option() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let args_struct = ArgsStruct::from(&ctx);
+
+        assert_eq!(
+            args_struct.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
@@ -1,40 +1,60 @@
+use crate::components::required_fields_doc;
 use crate::struct_builder::{BuilderContext, GenericsContext};
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{parse_quote, Fields, ItemStruct};
 
-pub struct BuilderStruct {
-    ctx: BuilderContext,
-    unit: bool
+pub struct BuilderStruct<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
 }
 
-impl From<&ItemStruct> for BuilderStruct {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let unit = matches!(value.fields, Fields::Unit);
-
-        Self { ctx, unit }
+impl<'a> From<&'a BuilderContext> for BuilderStruct<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
     }
 }
 
-impl ToTokens for BuilderStruct {
+impl ToTokens for BuilderStruct<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext {
             subject,
+            params,
             builder,
             builder_subject_field,
+            options,
             generics,
             ..
         } = &self.ctx;
         let GenericsContext {
-            generics_def,
+            generics_decl,
             generics_expr,
             where_clause,
+            ..
         } = &generics;
 
-        if !self.unit {
+        if !matches!(self.fields, Fields::Unit) {
+            let summary = format!(" Builder for [`{}`], created with [`{}::builder`] from a [`{}`].", subject, subject, params);
+            let required = format!(" {}", required_fields_doc(self.ctx));
+            let presets = (!options.presets.is_empty()).then(|| {
+                let presets = options.presets
+                    .iter()
+                    .map(|preset| format!("[`{}`](Self::{})", preset.name, preset.name))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                let presets = format!(" Presets: {}.", presets);
+                quote! {
+                    #[doc = ""]
+                    #[doc = #presets]
+                }
+            });
+
             let builder_struct: ItemStruct = parse_quote! {
-                pub struct #builder #generics_def #where_clause {
+                #[doc = #summary]
+                #[doc = ""]
+                #[doc = #required]
+                #presets
+                pub struct #builder #generics_decl #where_clause {
                     #builder_subject_field: #subject #generics_expr
                 }
             };
@@ -47,6 +67,7 @@ impl ToTokens for BuilderStruct {
 #[cfg(test)]
 mod tests {
     use crate::components::BuilderStruct;
+    use crate::struct_builder::BuilderContext;
     use crate::test_util::{sample_named_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
     use proc_macro::TokenStream;
     use quote::ToTokens;
@@ -56,6 +77,9 @@ mod tests {
     fn test_with_named_fields() {
         let item_struct = sample_named_item_struct();
         let expected: ItemStruct = parse_quote! {
+            #[doc = " Builder for [`MyStruct`], created with [`MyStruct::builder`] from a [`MyStructParams`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `public_field`, `private_field`, `dynamic`, `dynamic2`, `generic`, `generic_inline`, `generic_where`."]
             pub struct MyStructBuilder<T, I: Send, W>
             where
                 W: Sync
@@ -64,7 +88,8 @@ mod tests {
             }
         };
         
-        let builder_struct = BuilderStruct::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let builder_struct = BuilderStruct::from(&ctx);
 
         assert_eq!(
             builder_struct.to_token_stream().to_string(),
@@ -76,6 +101,9 @@ mod tests {
     fn test_with_unnamed_fields() {
         let item_struct = sample_unnamed_item_struct();
         let expected: ItemStruct = parse_quote! {
+            #[doc = " Builder for [`MyStruct`], created with [`MyStruct::builder`] from a [`MyStructParams`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `0`, `1`, `5`, `6`, `7`, `8`, `9`."]
             pub struct MyStructBuilder<T, I: Send, W>
             where
                 W: Sync
@@ -84,7 +112,8 @@ mod tests {
             }
         };
 
-        let builder_struct = BuilderStruct::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let builder_struct = BuilderStruct::from(&ctx);
 
         assert_eq!(
             builder_struct.to_token_stream().to_string(),
@@ -92,11 +121,67 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_with_generic_defaults() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct<T: Clone = String, const N: usize = 4> {
+                pub value: T,
+                pub values: [u8; N]
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " Builder for [`MyStruct`], created with [`MyStruct::builder`] from a [`MyStructParams`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `value`, `values`."]
+            pub struct MyStructBuilder<T: Clone = String, const N: usize = 4> {
+                inner: MyStruct<T, N>
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let builder_struct = BuilderStruct::from(&ctx);
+
+        assert_eq!(
+            builder_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_presets() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(preset(name = "local_dev", port = 8080), preset(name = "ci"))]
+            pub struct Config {
+                pub host: String,
+                pub port: Option<u16>
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " Builder for [`Config`], created with [`Config::builder`] from a [`ConfigParams`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `host`."]
+            #[doc = ""]
+            #[doc = " Presets: [`local_dev`](Self::local_dev), [`ci`](Self::ci)."]
+            pub struct ConfigBuilder {
+                inner: Config
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let builder_struct = BuilderStruct::from(&ctx);
+
+        assert_eq!(
+            builder_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
     #[test]
     fn test_with_unit_struct() {
         let item_struct = sample_unit_item_struct();
 
-        let builder_struct = BuilderStruct::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let builder_struct = BuilderStruct::from(&ctx);
 
         assert_eq!(
             builder_struct.to_token_stream().to_string(),
@@ -0,0 +1,83 @@
+use crate::components::has_fallible_params;
+use crate::struct_builder::BuilderContext;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{parse_quote, Fields, ItemExternCrate};
+
+pub struct ExternCrateAlloc<'a> {
+    ctx: &'a BuilderContext
+}
+
+impl<'a> From<&'a BuilderContext> for ExternCrateAlloc<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx }
+    }
+}
+
+impl ToTokens for ExternCrateAlloc<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext { alloc, .. } = &self.ctx;
+
+        if !needs_alloc(self.ctx) {
+            return;
+        }
+
+        // `alloc` is there for `std` and `no_std` crates alike, so the paths don't depend on which features are enabled
+        let extern_crate: ItemExternCrate = parse_quote! {
+            #[doc(hidden)]
+            extern crate alloc as #alloc;
+        };
+
+        extern_crate.to_tokens(tokens);
+    }
+}
+
+/// Whether the generated code allocates, i.e. has `String`s in the errors of `try_convert` or `set_by_name`.
+pub fn needs_alloc(ctx: &BuilderContext) -> bool {
+    has_fallible_params(ctx) || (ctx.options.set_by_name && !matches!(ctx.fields, Fields::Unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ExternCrateAlloc;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, ItemStruct};
+
+    #[test]
+    fn test_with_set_by_name() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(set_by_name)]
+            pub struct MyStruct {
+                pub name: String
+            }
+        };
+        let expected = quote! {
+            #[doc(hidden)]
+            extern crate alloc as __MyStruct_alloc;
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let extern_crate_alloc = ExternCrateAlloc::from(&ctx);
+
+        assert_eq!(
+            extern_crate_alloc.to_token_stream().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_allocating_options() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let extern_crate_alloc = ExternCrateAlloc::from(&ctx);
+
+        assert_eq!(
+            extern_crate_alloc.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
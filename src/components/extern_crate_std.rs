@@ -0,0 +1,84 @@
+use crate::components::needs_alloc;
+use crate::struct_builder::BuilderContext;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{parse_quote, ItemExternCrate};
+
+pub struct ExternCrateStd<'a> {
+    ctx: &'a BuilderContext
+}
+
+impl<'a> From<&'a BuilderContext> for ExternCrateStd<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx }
+    }
+}
+
+impl ToTokens for ExternCrateStd<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext { std, .. } = &self.ctx;
+
+        if !needs_std(self.ctx) {
+            return;
+        }
+
+        // A `no_std` crate can still link `std` where it's available, which the `std` feature says it is
+        let extern_crate: ItemExternCrate = parse_quote! {
+            #[doc(hidden)]
+            extern crate std as #std;
+        };
+
+        extern_crate.to_tokens(tokens);
+    }
+}
+
+/// Whether the generated code names `std`, i.e. has errors of `try_convert` or `set_by_name` that implement
+/// `std::error::Error`, which they only do with the `std` feature.
+pub fn needs_std(ctx: &BuilderContext) -> bool {
+    cfg!(feature = "std") && needs_alloc(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ExternCrateStd;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, ItemStruct};
+
+    #[test]
+    fn test_with_set_by_name() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(set_by_name)]
+            pub struct MyStruct {
+                pub name: String
+            }
+        };
+        let expected = quote! {
+            #[doc(hidden)]
+            extern crate std as __MyStruct_std;
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let extern_crate_std = ExternCrateStd::from(&ctx);
+
+        assert_eq!(
+            extern_crate_std.to_token_stream().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_allocating_options() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let extern_crate_std = ExternCrateStd::from(&ctx);
+
+        assert_eq!(
+            extern_crate_std.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
@@ -1,28 +1,30 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_members, cfg_stmts};
+use crate::components::{nested_builder_type, option_inner_type};
+use crate::options::FieldOptions;
 use crate::struct_builder::{BuilderContext, GenericsContext};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, ToTokens};
-use syn::{parse_quote, Fields, ImplItemFn, Index, ItemImpl, ItemStruct};
+use syn::spanned::Spanned;
+use syn::{parse_quote, parse_quote_spanned, Attribute, Expr, Field, Fields, GenericArgument, ImplItemFn, ItemImpl, Path, PathArguments, Token, Type, TypeParamBound, TypePath, TypeTraitObject};
 
-pub struct ImplBuilderFns {
-    ctx: BuilderContext,
-    fields: Fields
+pub struct ImplBuilderFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
 }
 
-impl From<&ItemStruct> for ImplBuilderFns {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let fields = value.fields.clone();
-
-        Self { ctx, fields }
+impl<'a> From<&'a BuilderContext> for ImplBuilderFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
     }
 }
 
-impl ToTokens for ImplBuilderFns {
+impl ToTokens for ImplBuilderFns<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext {
             subject,
             builder,
             builder_subject_field,
+            options,
             generics,
             ..
         } = &self.ctx;
@@ -31,17 +33,65 @@ impl ToTokens for ImplBuilderFns {
             Fields::Named(named_fields) => {
                 let fns = named_fields.named
                     .iter()
-                    .map(|field| {
+                    .zip(&self.ctx.field_contexts)
+                    .flat_map(|(field, field_ctx)| {
+                        let field_options = &field_ctx.options;
+                        if !field_options.has_setter() {
+                            return vec![];
+                        }
+
                         let field_ident = field.ident.as_ref().expect("named field missing ident");
                         let field_type = &field.ty;
-                        let fn_ident = format_ident!("with_{}", &field_ident);
+                        let span = field_span(field);
+                        let fn_ident = format_ident!("with_{}", &field_ident, span = span);
+                        let vis = field_options.setter_vis();
+                        let attrs = setter_attrs(field);
+                        let const_setter = field_options.has_const_setter(options.const_fn);
+                        let constness: Option<Token![const]> = const_setter.then(Default::default);
+                        let (value_type, value) = setter_value(field, field_options, const_setter);
 
-                        parse_quote! {
-                            pub fn #fn_ident(mut self, value: #field_type) -> Self {
-                                self.#builder_subject_field.#field_ident = value;
+                        let mut fns: Vec<ImplItemFn> = vec![parse_quote_spanned! {span=>
+                            #(#attrs)*
+                            #vis #constness fn #fn_ident(mut self, value: #value_type) -> Self {
+                                self.#builder_subject_field.#field_ident = #value;
                                 self
                             }
+                        }];
+
+                        // Trait object fields get a setter that does the wrapping, unless `boxed` makes `with_<field>` do it
+                        if let (false, Some((value_type, value))) = (field_options.boxed, trait_object_setter_value(field)) {
+                            let fn_ident = format_ident!("with_{}_boxed", &field_ident, span = span);
+                            fns.push(parse_quote_spanned! {span=>
+                                #(#attrs)*
+                                #vis fn #fn_ident(mut self, value: #value_type) -> Self {
+                                    self.#builder_subject_field.#field_ident = #value;
+                                    self
+                                }
+                            });
                         }
+
+                        // Setters of a flattened field's own fields go through the nested struct's builder
+                        if let (Some(flattened), Some(nested_builder)) = (&field_options.flatten, nested_builder_type(field_type)) {
+                            fns.extend(flattened.iter().map::<ImplItemFn, _>(|nested_field| {
+                                let nested_ident = nested_field.ident.as_ref().expect("named field missing ident");
+                                let nested_type = &nested_field.ty;
+                                let span = field_span(nested_field);
+                                let fn_ident = format_ident!("with_{}", &nested_ident, span = span);
+                                let attrs = setter_attrs(nested_field).into_iter().chain(cfg_attrs(field));
+
+                                parse_quote_spanned! {span=>
+                                    #(#attrs)*
+                                    #vis fn #fn_ident(mut self, value: #nested_type) -> Self {
+                                        self.#builder_subject_field.#field_ident = ::core::convert::Into::<#nested_builder>::into(self.#builder_subject_field.#field_ident)
+                                            .#fn_ident(value)
+                                            .build();
+                                        self
+                                    }
+                                }
+                            }));
+                        }
+
+                        fns
                     })
                     .collect::<Vec<ImplItemFn>>();
 
@@ -49,20 +99,53 @@ impl ToTokens for ImplBuilderFns {
             },
 
             Fields::Unnamed(unnamed_fields) => {
+                let fields = self.fields.iter().collect::<Vec<&Field>>();
                 let fns = unnamed_fields.unnamed
                     .iter()
+                    .zip(&self.ctx.field_contexts)
                     .enumerate()
-                    .map(|(i, field)| {
-                        let field_index = Index::from(i);
-                        let field_type = &field.ty;
-                        let fn_ident = format_ident!("with_{}", &field_index);
+                    .flat_map(|(i, (field, field_ctx))| {
+                        let field_options = &field_ctx.options;
+                        if !field_options.has_setter() {
+                            return vec![];
+                        }
+
+                        let span = field_span(field);
+                        let fn_ident = format_ident!("with_{}", i, span = span);
+                        let vis = field_options.setter_vis();
+                        let attrs = setter_attrs(field);
+                        let const_setter = field_options.has_const_setter(options.const_fn);
+                        let constness: Option<Token![const]> = const_setter.then(Default::default);
+                        let (value_type, value) = setter_value(field, field_options, const_setter);
+                        let members = cfg_members(&fields, i);
+                        let stmts = cfg_stmts(&members, |member| vec![parse_quote_spanned! {span=>
+                            self.#builder_subject_field.#member = #value;
+                        }]);
 
-                        parse_quote! {
-                            pub fn #fn_ident(mut self, value: #field_type) -> Self {
-                                self.#builder_subject_field.#field_index = value;
+                        let mut fns: Vec<ImplItemFn> = vec![parse_quote_spanned! {span=>
+                            #(#attrs)*
+                            #vis #constness fn #fn_ident(mut self, value: #value_type) -> Self {
+                                #(#stmts)*
                                 self
                             }
+                        }];
+
+                        // Trait object fields get a setter that does the wrapping, unless `boxed` makes `with_<index>` do it
+                        if let (false, Some((value_type, value))) = (field_options.boxed, trait_object_setter_value(field)) {
+                            let fn_ident = format_ident!("with_{}_boxed", i, span = span);
+                            let stmts = cfg_stmts(&members, |member| vec![parse_quote_spanned! {span=>
+                                self.#builder_subject_field.#member = #value;
+                            }]);
+                            fns.push(parse_quote_spanned! {span=>
+                                #(#attrs)*
+                                #vis fn #fn_ident(mut self, value: #value_type) -> Self {
+                                    #(#stmts)*
+                                    self
+                                }
+                            });
                         }
+
+                        fns
                     })
                     .collect::<Vec<ImplItemFn>>();
 
@@ -76,14 +159,23 @@ impl ToTokens for ImplBuilderFns {
             let GenericsContext {
                 generics_def,
                 generics_expr,
-                where_clause
+                where_clause,
+                ..
             } = &generics;
             
+            let allow_deprecated = allow_deprecated(self.fields);
+            // Moving the subject out of the builder isn't possible in a `const fn` if any of its fields needs dropping
+            let build_constness: Option<Token![const]> = self.ctx.field_contexts
+                .iter()
+                .all(|field_ctx| field_ctx.options.has_const_setter(options.const_fn))
+                .then(Default::default);
+
             let item_impl: ItemImpl = parse_quote! {
+                #allow_deprecated
                 impl #generics_def #builder #generics_expr #where_clause {
                     #(#functions)*
 
-                    pub fn build(self) -> #subject #generics_expr {
+                    pub #build_constness fn build(self) -> #subject #generics_expr {
                         self.#builder_subject_field
                     }
                 }
@@ -94,13 +186,113 @@ impl ToTokens for ImplBuilderFns {
     }
 }
 
+/// The type a field's setter takes and the value it sets. With `boxed`, `Box`, `Arc` and `Rc` fields (or an `Option` of
+/// one) take the pointed-to type, or `impl Trait` for trait objects, and do the wrapping.
+fn setter_value(field: &Field, options: &FieldOptions, const_fn: bool) -> (Type, Expr) {
+    let unchanged = (field.ty.clone(), parse_quote! { value });
+    if const_fn || !options.boxed {
+        return unchanged;
+    }
+
+    wrapping_setter_value(field).unwrap_or(unchanged)
+}
+
+/// The type and value of the additional `with_<field>_boxed` setter of a `Box`, `Arc` or `Rc` trait object field (or an
+/// `Option` of one), which takes `impl Trait` and does the wrapping.
+fn trait_object_setter_value(field: &Field) -> Option<(Type, Expr)> {
+    if !is_trait_object_pointer(&field.ty) {
+        return None;
+    }
+
+    wrapping_setter_value(field)
+}
+
+/// Whether the type is a `Box`, `Arc` or `Rc` of a trait object, or an `Option` of one.
+pub fn is_trait_object_pointer(ty: &Type) -> bool {
+    smart_pointer(option_inner_type(ty).unwrap_or(ty)).is_some_and(|(_, pointee)| matches!(pointee, Type::TraitObject(_)))
+}
+
+fn wrapping_setter_value(field: &Field) -> Option<(Type, Expr)> {
+    let optional_inner_type = option_inner_type(&field.ty);
+    let (pointer, pointee) = smart_pointer(optional_inner_type.unwrap_or(&field.ty))?;
+
+    let value_type: Type = match pointee {
+        // Trait objects in fields are `'static` unless they say otherwise, and so are the values wrapped into them
+        Type::TraitObject(TypeTraitObject { bounds, .. }) => {
+            if bounds.iter().any(|bound| matches!(bound, TypeParamBound::Lifetime(_))) {
+                parse_quote! { impl #bounds }
+            } else {
+                parse_quote! { impl #bounds + 'static }
+            }
+        },
+        _ => pointee.clone()
+    };
+    let value: Expr = if optional_inner_type.is_some() {
+        parse_quote! { ::core::option::Option::Some(#pointer::new(value)) }
+    } else {
+        parse_quote! { #pointer::new(value) }
+    };
+
+    Some((value_type, value))
+}
+
+/// The path of a `Box`, `Arc` or `Rc` type without its generic argument, and the type it points to.
+pub fn smart_pointer(ty: &Type) -> Option<(Path, &Type)> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last().filter(|seg| seg.ident == "Box" || seg.ident == "Arc" || seg.ident == "Rc")?;
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    let pointee = match arguments.args.first() {
+        Some(GenericArgument::Type(pointee)) if arguments.args.len() == 1 => pointee,
+        _ => return None
+    };
+
+    let mut pointer = path.clone();
+    pointer.segments.last_mut()?.arguments = PathArguments::None;
+
+    Some((pointer, pointee))
+}
+
+/// The span of code generated for a field. It's located at the field, so errors and go-to-definition lead to it, but it
+/// resolves names at the call site like the rest of the generated code.
+pub fn field_span(field: &Field) -> Span {
+    let located_at = match &field.ident {
+        Some(ident) => ident.span(),
+        None => field.ty.span()
+    };
+    Span::call_site().located_at(located_at)
+}
+
+/// The field's doc comments, `#[deprecated]` attribute and `#[cfg]`s, carried over to its setter.
+pub fn setter_attrs(field: &Field) -> Vec<Attribute> {
+    field.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("deprecated"))
+        .cloned()
+        .chain(cfg_attrs(field))
+        .collect()
+}
+
+/// Generated code using a deprecated field would warn where the macro is used, so it allows it.
+pub fn allow_deprecated(fields: &Fields) -> Option<Attribute> {
+    fields
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .any(|attr| attr.path().is_ident("deprecated"))
+        .then(|| parse_quote! { #[allow(deprecated)] })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::components::ImplBuilderFns;
-    use crate::test_util::{sample_named_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::{sample_named_item_struct, sample_skipped_named_item_struct, sample_skipped_unnamed_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
     use proc_macro::TokenStream;
     use quote::ToTokens;
-    use syn::{parse_quote, ItemImpl};
+    use syn::{parse_quote, ItemImpl, ItemStruct};
 
     #[test]
     fn test_with_named_fields() {
@@ -139,6 +331,11 @@ mod tests {
                     self.inner.dynamic = value;
                     self
                 }
+
+                pub fn with_dynamic_boxed(mut self, value: impl Send + 'static) -> Self {
+                    self.inner.dynamic = Box::new(value);
+                    self
+                }
                 
                 pub fn with_dynamic2(mut self, value: Box<Option<dyn Send>>) -> Self {
                     self.inner.dynamic2 = value;
@@ -166,7 +363,8 @@ mod tests {
             }
         };
 
-        let impl_subject_fn_builder = ImplBuilderFns::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_subject_fn_builder = ImplBuilderFns::from(&ctx);
 
         assert_eq!(
             impl_subject_fn_builder.to_token_stream().to_string(),
@@ -211,6 +409,11 @@ mod tests {
                     self.inner.5 = value;
                     self
                 }
+
+                pub fn with_5_boxed(mut self, value: impl Send + 'static) -> Self {
+                    self.inner.5 = Box::new(value);
+                    self
+                }
                 
                 pub fn with_6(mut self, value: Box<Option<dyn Send>>) -> Self {
                     self.inner.6 = value;
@@ -238,7 +441,8 @@ mod tests {
             }
         };
 
-        let subject_impl = ImplBuilderFns::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplBuilderFns::from(&ctx);
 
         assert_eq!(
             subject_impl.to_token_stream().to_string(),
@@ -246,11 +450,386 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_with_skipped_named_fields() {
+        let item_struct = sample_skipped_named_item_struct();
+        let expected: ItemImpl = parse_quote! {
+            impl<T> MyStructBuilder<T> {
+                pub fn with_required(mut self, value: String) -> Self {
+                    self.inner.required = value;
+                    self
+                }
+
+                pub fn with_optional(mut self, value: Option<String>) -> Self {
+                    self.inner.optional = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct<T> {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_skipped_unnamed_fields() {
+        let item_struct = sample_skipped_unnamed_item_struct();
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                pub fn with_1(mut self, value: String) -> Self {
+                    self.inner.1 = value;
+                    self
+                }
+
+                pub fn with_2(mut self, value: Option<u8>) -> Self {
+                    self.inner.2 = value;
+                    self
+                }
+
+                pub fn with_4(mut self, value: String) -> Self {
+                    self.inner.4 = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_cfg_unnamed_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct(#[cfg(feature = "a")] u8, Option<String>);
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                #[cfg(feature = "a")]
+                pub fn with_0(mut self, value: u8) -> Self {
+                    self.inner.0 = value;
+                    self
+                }
+
+                pub fn with_1(mut self, value: Option<String>) -> Self {
+                    #[cfg(not(feature = "a"))]
+                    {
+                        self.inner.0 = value;
+                    }
+                    #[cfg(feature = "a")]
+                    {
+                        self.inner.1 = value;
+                    }
+                    self
+                }
+
+                pub fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_setter_options() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(setter(skip))]
+                pub tenant_id: u64,
+                #[builder(setter(vis = "pub(crate)"))]
+                pub internal: Option<String>,
+                pub name: String
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                pub(crate) fn with_internal(mut self, value: Option<String>) -> Self {
+                    self.inner.internal = value;
+                    self
+                }
+
+                pub fn with_name(mut self, value: String) -> Self {
+                    self.inner.name = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_field_docs() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                /// The platypus' name.
+                #[serde(rename = "platypusName")]
+                pub name: String,
+                #[deprecated(note = "use `name`")]
+                pub nickname: Option<String>
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            #[allow(deprecated)]
+            impl MyStructBuilder {
+                /// The platypus' name.
+                pub fn with_name(mut self, value: String) -> Self {
+                    self.inner.name = value;
+                    self
+                }
+
+                #[deprecated(note = "use `name`")]
+                pub fn with_nickname(mut self, value: Option<String>) -> Self {
+                    self.inner.nickname = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_flattened_field() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub customer_id: u64,
+                #[builder(flatten(created_by: String, note: Option<String>))]
+                pub audit: AuditInfo,
+                pub comment: Option<String>
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                pub fn with_customer_id(mut self, value: u64) -> Self {
+                    self.inner.customer_id = value;
+                    self
+                }
+
+                pub fn with_audit(mut self, value: AuditInfo) -> Self {
+                    self.inner.audit = value;
+                    self
+                }
+
+                pub fn with_created_by(mut self, value: String) -> Self {
+                    self.inner.audit = ::core::convert::Into::<AuditInfoBuilder>::into(self.inner.audit)
+                        .with_created_by(value)
+                        .build();
+                    self
+                }
+
+                pub fn with_note(mut self, value: Option<String>) -> Self {
+                    self.inner.audit = ::core::convert::Into::<AuditInfoBuilder>::into(self.inner.audit)
+                        .with_note(value)
+                        .build();
+                    self
+                }
+
+                pub fn with_comment(mut self, value: Option<String>) -> Self {
+                    self.inner.comment = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_smart_pointer_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct<'a> {
+                #[builder(boxed)]
+                pub on_error: Box<dyn Fn(&Error) + Send>,
+                #[builder(boxed)]
+                pub handler: Option<std::sync::Arc<dyn Handler>>,
+                #[builder(boxed)]
+                pub logger: Rc<dyn Log + 'a>,
+                #[builder(boxed)]
+                pub config: Arc<Config>,
+                pub fallback: Box<dyn Handler>,
+                pub data: Box<[u8]>
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl<'a> MyStructBuilder<'a> {
+                pub fn with_on_error(mut self, value: impl Fn(&Error) + Send + 'static) -> Self {
+                    self.inner.on_error = Box::new(value);
+                    self
+                }
+
+                pub fn with_handler(mut self, value: impl Handler + 'static) -> Self {
+                    self.inner.handler = ::core::option::Option::Some(std::sync::Arc::new(value));
+                    self
+                }
+
+                pub fn with_logger(mut self, value: impl Log + 'a) -> Self {
+                    self.inner.logger = Rc::new(value);
+                    self
+                }
+
+                pub fn with_config(mut self, value: Config) -> Self {
+                    self.inner.config = Arc::new(value);
+                    self
+                }
+
+                pub fn with_fallback(mut self, value: Box<dyn Handler>) -> Self {
+                    self.inner.fallback = value;
+                    self
+                }
+
+                pub fn with_fallback_boxed(mut self, value: impl Handler + 'static) -> Self {
+                    self.inner.fallback = Box::new(value);
+                    self
+                }
+
+                pub fn with_data(mut self, value: Box<[u8]>) -> Self {
+                    self.inner.data = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct<'a> {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_const() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(const)]
+            pub struct MyStruct(pub u16, pub Option<u8>);
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                pub const fn with_0(mut self, value: u16) -> Self {
+                    self.inner.0 = value;
+                    self
+                }
+
+                pub const fn with_1(mut self, value: Option<u8>) -> Self {
+                    self.inner.1 = value;
+                    self
+                }
+
+                pub const fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_const_and_non_const_setter() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(const)]
+            pub struct MyStruct(pub u16, #[builder(setter(const = false))] pub Option<String>);
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                pub const fn with_0(mut self, value: u16) -> Self {
+                    self.inner.0 = value;
+                    self
+                }
+
+                pub fn with_1(mut self, value: Option<String>) -> Self {
+                    self.inner.1 = value;
+                    self
+                }
+
+                pub fn build(self) -> MyStruct {
+                    self.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
     #[test]
     fn test_with_unit_struct() {
         let item_struct = sample_unit_item_struct();
 
-        let impl_builder_fns = ImplBuilderFns::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_fns = ImplBuilderFns::from(&ctx);
 
         assert_eq!(
             impl_builder_fns.to_token_stream().to_string(),
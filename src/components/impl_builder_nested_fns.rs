@@ -0,0 +1,266 @@
+use crate::cfg_resolution::{cfg_members, cfg_stmts, CfgMember};
+use crate::components::{allow_deprecated, field_span, option_inner_type, setter_attrs};
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::{format_ident, IdentFragment, ToTokens};
+use syn::{parse_quote, parse_quote_spanned, Expr, Field, Fields, ImplItemFn, ItemImpl, Member, Path, PathArguments, Type, TypePath};
+
+pub struct ImplBuilderNestedFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplBuilderNestedFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplBuilderNestedFns<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            builder,
+            generics,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        let fields = self.fields.iter().collect::<Vec<&Field>>();
+        let functions = self.ctx.analysed_fields()
+            .enumerate()
+            .flat_map(|(i, (field, field_ctx))| {
+                if !field_ctx.options.nested || !field_ctx.options.has_setter() {
+                    return vec![];
+                }
+
+                let members = cfg_members(&fields, i);
+                match &field.ident {
+                    Some(ident) => self.nested_fns(ident, &members, field, field_ctx),
+                    None => self.nested_fns(i, &members, field, field_ctx)
+                }
+            })
+            .collect::<Vec<ImplItemFn>>();
+
+        if functions.is_empty() {
+            return;
+        }
+
+        let allow_deprecated = allow_deprecated(self.fields);
+
+        let item_impl: ItemImpl = parse_quote! {
+            #allow_deprecated
+            impl #generics_def #builder #generics_expr #where_clause {
+                #(#functions)*
+            }
+        };
+
+        item_impl.to_tokens(tokens);
+    }
+}
+
+impl<'a> ImplBuilderNestedFns<'a> {
+    fn nested_fns(&self, name: impl IdentFragment, members: &[CfgMember], field: &Field, field_ctx: &FieldContext) -> Vec<ImplItemFn> {
+        let builder_subject_field = &self.ctx.builder_subject_field;
+        let Some(nested_builder) = nested_builder_type(&field.ty) else {
+            return vec![];
+        };
+        let nested = option_inner_type(&field.ty).unwrap_or(&field.ty);
+        let span = field_span(field);
+        let vis = field_ctx.options.setter_vis();
+        // Docs are the setter's, but the other attributes apply to every method of the field
+        let attrs = setter_attrs(field)
+            .into_iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+        let with_builder_fn_ident = format_ident!("with_{}_builder", name, span = span);
+        let update_fn_ident = format_ident!("update_{}", name, span = span);
+
+        // The nested builder is opened over a value through its struct's `From<Params>` and `From<Struct> for Builder`
+        let built: Expr = parse_quote_spanned! {span=>
+            f(::core::convert::Into::<#nested_builder>::into(::core::convert::Into::<#nested>::into(params))).build()
+        };
+        let required = field_ctx.required;
+        let with_value: Expr = if required {
+            built
+        } else {
+            parse_quote_spanned! {span=> ::core::option::Option::Some(#built) }
+        };
+        let update_value = |member: &Member| -> Expr {
+            if required {
+                parse_quote_spanned! {span=>
+                    f(::core::convert::Into::<#nested_builder>::into(self.#builder_subject_field.#member)).build()
+                }
+            } else {
+                parse_quote_spanned! {span=>
+                    self.#builder_subject_field.#member.map(|value| f(::core::convert::Into::<#nested_builder>::into(value)).build())
+                }
+            }
+        };
+        let with_stmts = cfg_stmts(members, |member| vec![parse_quote_spanned! {span=>
+            self.#builder_subject_field.#member = #with_value;
+        }]);
+        let update_stmts = cfg_stmts(members, |member| {
+            let value = update_value(member);
+            vec![parse_quote_spanned! {span=> self.#builder_subject_field.#member = #value; }]
+        });
+
+        vec![
+            parse_quote_spanned! {span=>
+                #(#attrs)*
+                #vis fn #with_builder_fn_ident(
+                    mut self,
+                    params: impl ::core::convert::Into<#nested>,
+                    f: impl ::core::ops::FnOnce(#nested_builder) -> #nested_builder
+                ) -> Self {
+                    #(#with_stmts)*
+                    self
+                }
+            },
+            parse_quote_spanned! {span=>
+                #(#attrs)*
+                #vis fn #update_fn_ident(mut self, f: impl ::core::ops::FnOnce(#nested_builder) -> #nested_builder) -> Self {
+                    #(#update_stmts)*
+                    self
+                }
+            }
+        ]
+    }
+}
+
+/// The builder of a nested field's struct, i.e. `AddressBuilder` for `Address` or `Option<Address>`.
+pub fn nested_builder_type(ty: &Type) -> Option<Type> {
+    let ty = option_inner_type(ty).unwrap_or(ty);
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let mut path = path.clone();
+    let segment = path.segments.last_mut()?;
+    segment.ident = format_ident!("{}Builder", segment.ident);
+
+    Some(Type::Path(TypePath { qself: None, path }))
+}
+
+/// The path of a nested field's params struct without generic arguments, i.e. `AddressParams` for `Address`, to be
+/// used in struct expressions.
+pub fn nested_params_path(ty: &Type) -> Option<Path> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let mut path = path.clone();
+    let segment = path.segments.last_mut()?;
+    segment.ident = format_ident!("{}Params", segment.ident);
+    segment.arguments = PathArguments::None;
+
+    Some(path)
+}
+
+/// The path of a nested field's field info struct, i.e. `AddressFieldInfo` for `Address`, whose constants don't depend
+/// on generic arguments.
+pub fn nested_field_info_path(ty: &Type) -> Option<Path> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    let mut path = path.clone();
+    let segment = path.segments.last_mut()?;
+    segment.ident = format_ident!("{}FieldInfo", segment.ident);
+    segment.arguments = PathArguments::None;
+
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{nested_builder_type, ImplBuilderNestedFns};
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, ItemImpl, ItemStruct, Type};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(nested)]
+                pub address: Address,
+                #[builder(nested, setter(vis = "pub(crate)"))]
+                pub billing: Option<geo::Address>,
+                pub name: String
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                pub fn with_address_builder(
+                    mut self,
+                    params: impl ::core::convert::Into<Address>,
+                    f: impl ::core::ops::FnOnce(AddressBuilder) -> AddressBuilder
+                ) -> Self {
+                    self.inner.address = f(::core::convert::Into::<AddressBuilder>::into(::core::convert::Into::<Address>::into(params))).build();
+                    self
+                }
+
+                pub fn update_address(mut self, f: impl ::core::ops::FnOnce(AddressBuilder) -> AddressBuilder) -> Self {
+                    self.inner.address = f(::core::convert::Into::<AddressBuilder>::into(self.inner.address)).build();
+                    self
+                }
+
+                pub(crate) fn with_billing_builder(
+                    mut self,
+                    params: impl ::core::convert::Into<geo::Address>,
+                    f: impl ::core::ops::FnOnce(geo::AddressBuilder) -> geo::AddressBuilder
+                ) -> Self {
+                    self.inner.billing = ::core::option::Option::Some(
+                        f(::core::convert::Into::<geo::AddressBuilder>::into(::core::convert::Into::<geo::Address>::into(params))).build()
+                    );
+                    self
+                }
+
+                pub(crate) fn update_billing(mut self, f: impl ::core::ops::FnOnce(geo::AddressBuilder) -> geo::AddressBuilder) -> Self {
+                    self.inner.billing = self.inner.billing.map(|value| f(::core::convert::Into::<geo::AddressBuilder>::into(value)).build());
+                    self
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_nested_fns = ImplBuilderNestedFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_nested_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_nested_fields() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_nested_fns = ImplBuilderNestedFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_nested_fns.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+
+    #[test]
+    fn test_nested_builder_type() {
+        let ty: Type = parse_quote! { Option<crate::Wrapper<u8>> };
+        let expected: Type = parse_quote! { crate::WrapperBuilder<u8> };
+
+        assert_eq!(
+            nested_builder_type(&ty).unwrap().to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+        assert!(nested_builder_type(&parse_quote! { [u8; 4] }).is_none());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::cfg_resolution::cfg_attrs;
+use crate::components::{allow_deprecated, has_fallible_params, tokens_text};
+use crate::options::PresetOptions;
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{parse_quote, Expr, ExprCall, ExprPath, Fields, ImplItemFn, ItemImpl, Stmt, Type};
+
+pub struct ImplBuilderPresetFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplBuilderPresetFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplBuilderPresetFns<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            params,
+            params_error,
+            params_argument,
+            builder,
+            builder_subject_field,
+            options,
+            generics,
+            fields_metadata,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        if options.presets.is_empty() || !matches!(self.fields, Fields::Named(_)) {
+            return;
+        }
+
+        let fallible = has_fallible_params(self.ctx);
+        let include_params_generics = fields_metadata.generic_required_fields_count > 0;
+        let params_type: Type = if include_params_generics {
+            parse_quote! { #params #generics_expr }
+        } else {
+            parse_quote! { #params }
+        };
+
+        let functions = options.presets
+            .iter()
+            .map::<ImplItemFn, _>(|preset| {
+                let PresetOptions { name, values } = preset;
+                let stmts = values
+                    .iter()
+                    .filter_map(|(ident, expr)| {
+                        let (field, field_ctx) = self.ctx.analysed_fields().find(|(field, _)| field.ident.as_ref() == Some(ident))?;
+                        let value = preset_value(field_ctx, expr);
+                        let cfg_attrs = cfg_attrs(field);
+                        Some(parse_quote! { #(#cfg_attrs)* builder.#builder_subject_field.#ident = #value; })
+                    })
+                    .collect::<Vec<Stmt>>();
+                let doc = format!(" Creates a [`{}`] from the `{}` preset. {}", builder, name, preset_values_doc(preset));
+
+                if fallible {
+                    parse_quote! {
+                        #[doc = #doc]
+                        pub fn #name(#params_argument: #params_type) -> ::core::result::Result<Self, #params_error> {
+                            let mut builder = #subject::builder(#params_argument)?;
+                            #(#stmts)*
+                            ::core::result::Result::Ok(builder)
+                        }
+                    }
+                } else {
+                    parse_quote! {
+                        #[doc = #doc]
+                        pub fn #name(#params_argument: #params_type) -> Self {
+                            let mut builder = #subject::builder(#params_argument);
+                            #(#stmts)*
+                            builder
+                        }
+                    }
+                }
+            })
+            .collect::<Vec<ImplItemFn>>();
+
+        let allow_deprecated = allow_deprecated(self.fields);
+
+        let item_impl: ItemImpl = parse_quote! {
+            #allow_deprecated
+            impl #generics_def #builder #generics_expr #where_clause {
+                #(#functions)*
+            }
+        };
+
+        item_impl.to_tokens(tokens);
+    }
+}
+
+/// The value a preset gives a field, wrapped in `Some` for optional fields unless it's already `Some(..)` or `None`.
+fn preset_value(field_ctx: &FieldContext, expr: &Expr) -> Expr {
+    if field_ctx.required || field_ctx.options.is_skipped() || is_option_expr(expr) {
+        expr.clone()
+    } else {
+        parse_quote! { ::core::option::Option::Some(#expr) }
+    }
+}
+
+fn is_option_expr(expr: &Expr) -> bool {
+    let path = match expr {
+        Expr::Call(ExprCall { func, .. }) => match func.as_ref() {
+            Expr::Path(ExprPath { path, .. }) => path,
+            _ => return false
+        },
+        Expr::Path(ExprPath { path, .. }) => path,
+        _ => return false
+    };
+
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Some" || segment.ident == "None")
+}
+
+/// Lists the values a preset gives, for the docs of its function.
+pub fn preset_values_doc(preset: &PresetOptions) -> String {
+    let values = preset.values
+        .iter()
+        .map(|(ident, expr)| format!("`{} = {}`", ident, tokens_text(expr)))
+        .collect::<Vec<String>>();
+
+    if values.is_empty() {
+        "It sets no fields.".to_owned()
+    } else {
+        format!("It sets {}.", values.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ImplBuilderPresetFns;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, ItemImpl, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(preset(name = "local_dev", age = 30, first_name = Some("Dev".into()), cached = 1))]
+            pub struct MyStruct {
+                pub name: String,
+                pub age: Option<u8>,
+                pub first_name: Option<String>,
+                #[builder(skip)]
+                pub cached: u64
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStructBuilder {
+                #[doc = " Creates a [`MyStructBuilder`] from the `local_dev` preset. It sets `age = 30`, `first_name = Some(\"Dev\".into())`, `cached = 1`."]
+                pub fn local_dev(params: MyStructParams) -> Self {
+                    let mut builder = MyStruct::builder(params);
+                    builder.inner.age = ::core::option::Option::Some(30);
+                    builder.inner.first_name = Some("Dev".into());
+                    builder.inner.cached = 1;
+                    builder
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_preset_fns = ImplBuilderPresetFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_preset_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_presets() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_preset_fns = ImplBuilderPresetFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_preset_fns.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
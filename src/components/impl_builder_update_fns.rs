@@ -0,0 +1,445 @@
+use crate::cfg_resolution::{cfg_members, cfg_stmts, CfgMember};
+use crate::components::{allow_deprecated, field_span, setter_attrs};
+use crate::options::FieldOptions;
+use crate::struct_builder::{BuilderContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::{format_ident, IdentFragment, ToTokens};
+use syn::{parse_quote, parse_quote_spanned, Field, Fields, ImplItemFn, ItemImpl, Member};
+
+pub struct ImplBuilderUpdateFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplBuilderUpdateFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplBuilderUpdateFns<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            builder,
+            builder_subject_field,
+            generics,
+            ..
+        } = &self.ctx;
+
+        let optional_functions: Option<Vec<ImplItemFn>> = match &self.fields {
+            Fields::Named(named_fields) => {
+                let fns = named_fields.named
+                    .iter()
+                    .zip(&self.ctx.field_contexts)
+                    .flat_map(|(field, field_ctx)| {
+                        let options = &field_ctx.options;
+                        if !options.has_setter() {
+                            return vec![];
+                        }
+
+                        let field_ident = field.ident.as_ref().expect("named field missing ident");
+                        let members = vec![CfgMember { cfg: None, member: Member::from(field_ident.clone()) }];
+                        self.update_fns(field_ident, &members, field, options)
+                    })
+                    .collect::<Vec<ImplItemFn>>();
+
+                Some(fns)
+            },
+
+            Fields::Unnamed(unnamed_fields) => {
+                let fields = self.fields.iter().collect::<Vec<&Field>>();
+                let fns = unnamed_fields.unnamed
+                    .iter()
+                    .zip(&self.ctx.field_contexts)
+                    .enumerate()
+                    .flat_map(|(i, (field, field_ctx))| {
+                        let options = &field_ctx.options;
+                        if !options.has_setter() {
+                            return vec![];
+                        }
+
+                        self.update_fns(i, &cfg_members(&fields, i), field, options)
+                    })
+                    .collect::<Vec<ImplItemFn>>();
+
+                Some(fns)
+            },
+
+            Fields::Unit => None
+        };
+
+        if let Some(functions) = optional_functions {
+            let GenericsContext {
+                generics_def,
+                generics_expr,
+                where_clause,
+                ..
+            } = &generics;
+            let allow_deprecated = allow_deprecated(self.fields);
+
+            let item_impl: ItemImpl = parse_quote! {
+                #allow_deprecated
+                impl #generics_def #builder #generics_expr #where_clause {
+                    #(#functions)*
+
+                    pub fn modify(mut self, f: impl ::core::ops::FnOnce(&mut #subject #generics_expr)) -> Self {
+                        f(&mut self.#builder_subject_field);
+                        self
+                    }
+                }
+            };
+
+            item_impl.to_tokens(tokens);
+        }
+    }
+}
+
+impl<'a> ImplBuilderUpdateFns<'a> {
+    fn update_fns(&self, name: impl IdentFragment, members: &[CfgMember], field: &Field, options: &FieldOptions) -> Vec<ImplItemFn> {
+        let builder_subject_field = &self.ctx.builder_subject_field;
+        let field_type = &field.ty;
+        let span = field_span(field);
+        let vis = options.setter_vis();
+        // Docs are the setter's, but the other attributes apply to every method of the field
+        let attrs = setter_attrs(field)
+            .into_iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+        let map_fn_ident = format_ident!("map_{}", name, span = span);
+        let with_if_fn_ident = format_ident!("with_{}_if", name, span = span);
+        let maybe_with_fn_ident = format_ident!("maybe_with_{}", name, span = span);
+        let map_stmts = cfg_stmts(members, |member| vec![parse_quote_spanned! {span=>
+            self.#builder_subject_field.#member = f(self.#builder_subject_field.#member);
+        }]);
+        let assign_stmts = cfg_stmts(members, |member| vec![parse_quote_spanned! {span=>
+            self.#builder_subject_field.#member = value;
+        }]);
+
+        vec![
+            parse_quote_spanned! {span=>
+                #(#attrs)*
+                #vis fn #map_fn_ident(mut self, f: impl ::core::ops::FnOnce(#field_type) -> #field_type) -> Self {
+                    #(#map_stmts)*
+                    self
+                }
+            },
+            parse_quote_spanned! {span=>
+                #(#attrs)*
+                #vis fn #with_if_fn_ident(mut self, condition: bool, value: #field_type) -> Self {
+                    if condition {
+                        #(#assign_stmts)*
+                    }
+                    self
+                }
+            },
+            parse_quote_spanned! {span=>
+                #(#attrs)*
+                #vis fn #maybe_with_fn_ident(mut self, value: ::core::option::Option<#field_type>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        #(#assign_stmts)*
+                    }
+                    self
+                }
+            }
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ImplBuilderUpdateFns;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::{sample_named_item_struct, sample_unit_item_struct};
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, ItemImpl, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct = sample_named_item_struct();
+        let expected: ItemImpl = parse_quote! {
+            impl<T, I: Send, W> MyStructBuilder<T, I, W>
+            where
+                W: Sync
+            {
+                pub fn map_public_field(mut self, f: impl ::core::ops::FnOnce(String) -> String) -> Self {
+                    self.inner.public_field = f(self.inner.public_field);
+                    self
+                }
+
+                pub fn with_public_field_if(mut self, condition: bool, value: String) -> Self {
+                    if condition {
+                        self.inner.public_field = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_public_field(mut self, value: ::core::option::Option<String>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.public_field = value;
+                    }
+                    self
+                }
+
+                pub fn map_private_field(mut self, f: impl ::core::ops::FnOnce(String) -> String) -> Self {
+                    self.inner.private_field = f(self.inner.private_field);
+                    self
+                }
+
+                pub fn with_private_field_if(mut self, condition: bool, value: String) -> Self {
+                    if condition {
+                        self.inner.private_field = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_private_field(mut self, value: ::core::option::Option<String>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.private_field = value;
+                    }
+                    self
+                }
+
+                pub fn map_optional(mut self, f: impl ::core::ops::FnOnce(Option<usize>) -> Option<usize>) -> Self {
+                    self.inner.optional = f(self.inner.optional);
+                    self
+                }
+
+                pub fn with_optional_if(mut self, condition: bool, value: Option<usize>) -> Self {
+                    if condition {
+                        self.inner.optional = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_optional(mut self, value: ::core::option::Option<Option<usize> >) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.optional = value;
+                    }
+                    self
+                }
+
+                pub fn map_test(mut self, f: impl ::core::ops::FnOnce(std::option::Option<String>) -> std::option::Option<String>) -> Self {
+                    self.inner.test = f(self.inner.test);
+                    self
+                }
+
+                pub fn with_test_if(mut self, condition: bool, value: std::option::Option<String>) -> Self {
+                    if condition {
+                        self.inner.test = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_test(mut self, value: ::core::option::Option<std::option::Option<String> >) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.test = value;
+                    }
+                    self
+                }
+
+                pub fn map_test2(mut self, f: impl ::core::ops::FnOnce(option::Option<T>) -> option::Option<T>) -> Self {
+                    self.inner.test2 = f(self.inner.test2);
+                    self
+                }
+
+                pub fn with_test2_if(mut self, condition: bool, value: option::Option<T>) -> Self {
+                    if condition {
+                        self.inner.test2 = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_test2(mut self, value: ::core::option::Option<option::Option<T> >) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.test2 = value;
+                    }
+                    self
+                }
+
+                pub fn map_dynamic(mut self, f: impl ::core::ops::FnOnce(Box<dyn Send>) -> Box<dyn Send>) -> Self {
+                    self.inner.dynamic = f(self.inner.dynamic);
+                    self
+                }
+
+                pub fn with_dynamic_if(mut self, condition: bool, value: Box<dyn Send>) -> Self {
+                    if condition {
+                        self.inner.dynamic = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_dynamic(mut self, value: ::core::option::Option<Box<dyn Send> >) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.dynamic = value;
+                    }
+                    self
+                }
+
+                pub fn map_dynamic2(mut self, f: impl ::core::ops::FnOnce(Box<Option<dyn Send> >) -> Box<Option<dyn Send> >) -> Self {
+                    self.inner.dynamic2 = f(self.inner.dynamic2);
+                    self
+                }
+
+                pub fn with_dynamic2_if(mut self, condition: bool, value: Box<Option<dyn Send> >) -> Self {
+                    if condition {
+                        self.inner.dynamic2 = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_dynamic2(mut self, value: ::core::option::Option<Box<Option<dyn Send> > >) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.dynamic2 = value;
+                    }
+                    self
+                }
+
+                pub fn map_generic(mut self, f: impl ::core::ops::FnOnce(T) -> T) -> Self {
+                    self.inner.generic = f(self.inner.generic);
+                    self
+                }
+
+                pub fn with_generic_if(mut self, condition: bool, value: T) -> Self {
+                    if condition {
+                        self.inner.generic = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_generic(mut self, value: ::core::option::Option<T>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.generic = value;
+                    }
+                    self
+                }
+
+                pub fn map_generic_inline(mut self, f: impl ::core::ops::FnOnce(I) -> I) -> Self {
+                    self.inner.generic_inline = f(self.inner.generic_inline);
+                    self
+                }
+
+                pub fn with_generic_inline_if(mut self, condition: bool, value: I) -> Self {
+                    if condition {
+                        self.inner.generic_inline = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_generic_inline(mut self, value: ::core::option::Option<I>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.generic_inline = value;
+                    }
+                    self
+                }
+
+                pub fn map_generic_where(mut self, f: impl ::core::ops::FnOnce(W) -> W) -> Self {
+                    self.inner.generic_where = f(self.inner.generic_where);
+                    self
+                }
+
+                pub fn with_generic_where_if(mut self, condition: bool, value: W) -> Self {
+                    if condition {
+                        self.inner.generic_where = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_generic_where(mut self, value: ::core::option::Option<W>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.generic_where = value;
+                    }
+                    self
+                }
+
+                pub fn modify(mut self, f: impl ::core::ops::FnOnce(&mut MyStruct<T, I, W>)) -> Self {
+                    f(&mut self.inner);
+                    self
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_update_fns = ImplBuilderUpdateFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_update_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_unnamed_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct<T>(pub String, Option<T>);
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl<T> MyStructBuilder<T> {
+                pub fn map_0(mut self, f: impl ::core::ops::FnOnce(String) -> String) -> Self {
+                    self.inner.0 = f(self.inner.0);
+                    self
+                }
+
+                pub fn with_0_if(mut self, condition: bool, value: String) -> Self {
+                    if condition {
+                        self.inner.0 = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_0(mut self, value: ::core::option::Option<String>) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.0 = value;
+                    }
+                    self
+                }
+
+                pub fn map_1(mut self, f: impl ::core::ops::FnOnce(Option<T>) -> Option<T>) -> Self {
+                    self.inner.1 = f(self.inner.1);
+                    self
+                }
+
+                pub fn with_1_if(mut self, condition: bool, value: Option<T>) -> Self {
+                    if condition {
+                        self.inner.1 = value;
+                    }
+                    self
+                }
+
+                pub fn maybe_with_1(mut self, value: ::core::option::Option<Option<T> >) -> Self {
+                    if let ::core::option::Option::Some(value) = value {
+                        self.inner.1 = value;
+                    }
+                    self
+                }
+
+                pub fn modify(mut self, f: impl ::core::ops::FnOnce(&mut MyStruct<T>)) -> Self {
+                    f(&mut self.inner);
+                    self
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_update_fns = ImplBuilderUpdateFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_update_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_unit_struct() {
+        let item_struct = sample_unit_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_builder_update_fns = ImplBuilderUpdateFns::from(&ctx);
+
+        assert_eq!(
+            impl_builder_update_fns.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
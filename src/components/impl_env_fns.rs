@@ -0,0 +1,351 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_members, cfg_stmts, CfgMember};
+use crate::components::{allow_deprecated, field_span, from_str_type};
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, parse_quote_spanned, Attribute, Expr, Field, Fields, ItemEnum, ItemImpl, LitStr, Member, Stmt, Token, WherePredicate};
+
+pub struct ImplEnvFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplEnvFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplEnvFns<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            params,
+            params_argument,
+            builder,
+            builder_subject_field,
+            env_error,
+            options,
+            generics,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        let Some(env) = &options.env else {
+            return;
+        };
+        if matches!(self.fields, Fields::Unit) {
+            return;
+        }
+
+        let env_fields = self.ctx.analysed_fields()
+            .enumerate()
+            .filter(|(_, (_, field_ctx))| field_ctx.options.is_read_from_env())
+            .map(|(i, (field, field_ctx))| {
+                let member = match &field.ident {
+                    Some(ident) => Member::from(ident.clone()),
+                    None => Member::from(i)
+                };
+                let name = field_ctx.options.field_name(i, field);
+                let variable = LitStr::new(&format!("{}{}", env.prefix, name.to_uppercase()), proc_macro2::Span::call_site());
+                (member, variable, field, field_ctx)
+            })
+            .collect::<Vec<(Member, LitStr, &Field, &FieldContext)>>();
+
+        // Generic fields need their parsed type bounded, other fields are checked where they're parsed
+        let predicates = env_fields
+            .iter()
+            .filter(|(_, _, _, field_ctx)| field_ctx.generic)
+            .flat_map::<[WherePredicate; 2], _>(|(_, _, field, _)| {
+                let span = field_span(field);
+                let ty = from_str_type(field);
+                [
+                    parse_quote_spanned! {span=> #ty: ::core::str::FromStr },
+                    parse_quote_spanned! {span=> <#ty as ::core::str::FromStr>::Err: ::core::fmt::Display }
+                ]
+            })
+            .collect::<Punctuated<WherePredicate, Token![,]>>();
+
+        let subject_fields = self.fields.iter().collect::<Vec<&Field>>();
+        let apply_stmts = env_fields
+            .iter()
+            .filter(|(_, _, _, field_ctx)| field_ctx.options.has_setter())
+            .map::<Stmt, _>(|(member, variable, field, field_ctx)| {
+                let span = field_span(field);
+                let ty = from_str_type(field);
+                let value: Expr = if field_ctx.required {
+                    parse_quote_spanned! {span=> value }
+                } else {
+                    parse_quote_spanned! {span=> ::core::option::Option::Some(value) }
+                };
+
+                // Fields with `#[cfg]`s before a tuple field shift its index
+                let members = match member {
+                    Member::Unnamed(index) => cfg_members(&subject_fields, index.index as usize),
+                    Member::Named(_) => vec![CfgMember { cfg: None, member: member.clone() }]
+                };
+                let assignments = cfg_stmts(&members, |member| vec![parse_quote_spanned! {span=>
+                    self.#builder_subject_field.#member = #value;
+                }]);
+                let attrs = cfg_attrs(field);
+
+                parse_quote_spanned! {span=>
+                    #(#attrs)*
+                    if let ::core::option::Option::Some(value) = #env_error::read_var::<#ty>(#variable)? {
+                        #(#assignments)*
+                    }
+                }
+            })
+            .collect::<Vec<Stmt>>();
+
+        let required_values = env_fields
+            .iter()
+            .filter(|(_, _, _, field_ctx)| field_ctx.required)
+            .map(|(member, variable, field, _)| {
+                let span = field_span(field);
+                let ty = &field.ty;
+                let value: Expr = parse_quote_spanned! {span=>
+                    #env_error::read_var::<#ty>(#variable)?
+                        .ok_or(#env_error::Missing { variable: #variable })?
+                };
+                (member, cfg_attrs(field), value)
+            })
+            .collect::<Vec<(&Member, Vec<Attribute>, Expr)>>();
+
+        let params_expr: Expr = match &self.fields {
+            Fields::Named(_) => {
+                let values = required_values
+                    .iter()
+                    .map(|(member, attrs, value)| quote! { #(#attrs)* #member: #value });
+                parse_quote! { #params { #(#values),* } }
+            },
+            _ => {
+                let values = required_values
+                    .iter()
+                    .map(|(_, attrs, value)| quote! { #(#attrs)* #value });
+                parse_quote! { #params(#(#values),*) }
+            }
+        };
+
+        let error_enum: ItemEnum = parse_quote! {
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub enum #env_error {
+                Missing {
+                    variable: &'static str
+                },
+                Invalid {
+                    variable: &'static str,
+                    value: ::std::string::String,
+                    message: ::std::string::String
+                }
+            }
+        };
+
+        let error_impls: Vec<ItemImpl> = vec![
+            parse_quote! {
+                impl #env_error {
+                    fn read_var<T>(variable: &'static str) -> ::core::result::Result<::core::option::Option<T>, Self>
+                    where
+                        T: ::core::str::FromStr,
+                        <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                    {
+                        match ::std::env::var(variable) {
+                            ::core::result::Result::Ok(value) => match T::from_str(&value) {
+                                ::core::result::Result::Ok(parsed) => ::core::result::Result::Ok(::core::option::Option::Some(parsed)),
+                                ::core::result::Result::Err(error) => ::core::result::Result::Err(Self::Invalid {
+                                    variable,
+                                    value,
+                                    message: ::std::string::ToString::to_string(&error)
+                                })
+                            },
+                            ::core::result::Result::Err(::std::env::VarError::NotPresent) => ::core::result::Result::Ok(::core::option::Option::None),
+                            ::core::result::Result::Err(::std::env::VarError::NotUnicode(value)) => ::core::result::Result::Err(Self::Invalid {
+                                variable,
+                                value: value.to_string_lossy().into_owned(),
+                                message: ::std::string::ToString::to_string("not valid unicode")
+                            })
+                        }
+                    }
+                }
+            },
+            parse_quote! {
+                impl ::core::fmt::Display for #env_error {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match self {
+                            Self::Missing { variable } =>
+                                ::core::write!(f, "environment variable `{}` is not set", variable),
+                            Self::Invalid { variable, value, message } =>
+                                ::core::write!(f, "environment variable `{}` has an invalid value `{}`: {}", variable, value, message)
+                        }
+                    }
+                }
+            },
+            parse_quote! {
+                impl ::std::error::Error for #env_error {}
+            }
+        ];
+
+        let allow_deprecated = allow_deprecated(self.fields);
+        let builder_impl: ItemImpl = parse_quote! {
+            #allow_deprecated
+            impl #generics_def #builder #generics_expr #where_clause {
+                pub fn apply_env(mut self) -> ::core::result::Result<Self, #env_error>
+                where
+                    #predicates
+                {
+                    #(#apply_stmts)*
+                    ::core::result::Result::Ok(self)
+                }
+            }
+        };
+
+        let subject_impl: ItemImpl = parse_quote! {
+            #allow_deprecated
+            impl #generics_def #subject #generics_expr #where_clause {
+                pub fn from_env() -> ::core::result::Result<Self, #env_error>
+                where
+                    #predicates
+                {
+                    let #params_argument = #params_expr;
+                    Self::builder(#params_argument).apply_env().map(#builder::build)
+                }
+            }
+        };
+
+        error_enum.to_tokens(tokens);
+        error_impls.iter().for_each(|item_impl| item_impl.to_tokens(tokens));
+        builder_impl.to_tokens(tokens);
+        subject_impl.to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ImplEnvFns;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(env(prefix = "APP_"))]
+            pub struct MyStruct<T> {
+                pub r#type: T,
+                pub first_name: Option<String>,
+                #[builder(skip)]
+                pub cached: u64
+            }
+        };
+        let expected = quote! {
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub enum MyStructEnvError {
+                Missing {
+                    variable: &'static str
+                },
+                Invalid {
+                    variable: &'static str,
+                    value: ::std::string::String,
+                    message: ::std::string::String
+                }
+            }
+
+            impl MyStructEnvError {
+                fn read_var<T>(variable: &'static str) -> ::core::result::Result<::core::option::Option<T>, Self>
+                where
+                    T: ::core::str::FromStr,
+                    <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                {
+                    match ::std::env::var(variable) {
+                        ::core::result::Result::Ok(value) => match T::from_str(&value) {
+                            ::core::result::Result::Ok(parsed) => ::core::result::Result::Ok(::core::option::Option::Some(parsed)),
+                            ::core::result::Result::Err(error) => ::core::result::Result::Err(Self::Invalid {
+                                variable,
+                                value,
+                                message: ::std::string::ToString::to_string(&error)
+                            })
+                        },
+                        ::core::result::Result::Err(::std::env::VarError::NotPresent) => ::core::result::Result::Ok(::core::option::Option::None),
+                        ::core::result::Result::Err(::std::env::VarError::NotUnicode(value)) => ::core::result::Result::Err(Self::Invalid {
+                            variable,
+                            value: value.to_string_lossy().into_owned(),
+                            message: ::std::string::ToString::to_string("not valid unicode")
+                        })
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for MyStructEnvError {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        Self::Missing { variable } =>
+                            ::core::write!(f, "environment variable `{}` is not set", variable),
+                        Self::Invalid { variable, value, message } =>
+                            ::core::write!(f, "environment variable `{}` has an invalid value `{}`: {}", variable, value, message)
+                    }
+                }
+            }
+
+            impl ::std::error::Error for MyStructEnvError {}
+
+            impl<T> MyStructBuilder<T> {
+                pub fn apply_env(mut self) -> ::core::result::Result<Self, MyStructEnvError>
+                where
+                    T: ::core::str::FromStr,
+                    <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                {
+                    if let ::core::option::Option::Some(value) = MyStructEnvError::read_var::<T>("APP_TYPE")? {
+                        self.inner.r#type = value;
+                    }
+                    if let ::core::option::Option::Some(value) = MyStructEnvError::read_var::<String>("APP_FIRST_NAME")? {
+                        self.inner.first_name = ::core::option::Option::Some(value);
+                    }
+                    ::core::result::Result::Ok(self)
+                }
+            }
+
+            impl<T> MyStruct<T> {
+                pub fn from_env() -> ::core::result::Result<Self, MyStructEnvError>
+                where
+                    T: ::core::str::FromStr,
+                    <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                {
+                    let params = MyStructParams {
+                        r#type: MyStructEnvError::read_var::<T>("APP_TYPE")?
+                            .ok_or(MyStructEnvError::Missing { variable: "APP_TYPE" })?
+                    };
+                    Self::builder(params).apply_env().map(MyStructBuilder::build)
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_env_fns = ImplEnvFns::from(&ctx);
+
+        assert_eq!(
+            impl_env_fns.to_token_stream().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_env_option() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_env_fns = ImplEnvFns::from(&ctx);
+
+        assert_eq!(
+            impl_env_fns.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
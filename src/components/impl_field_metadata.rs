@@ -0,0 +1,503 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_condition};
+use crate::components::{field_span, is_required, nested_field_info_path};
+use crate::struct_builder::{BuilderContext, FieldContext, FieldsMetadata, GenericsContext};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Attribute, Expr, ExprLit, Field, Fields, ItemImpl, ItemStruct, Lit, LitStr, Meta, MetaNameValue, Type};
+
+pub struct ImplFieldMetadata<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplFieldMetadata<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplFieldMetadata<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            params,
+            builder,
+            field_info,
+            generics,
+            fields_metadata,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        if matches!(self.fields, Fields::Unit) {
+            return;
+        }
+
+        let [
+            required_fields_count,
+            optional_fields_count,
+            generic_required_fields_count,
+            generic_optional_fields_count,
+            skipped_fields_count
+        ] = self.field_counts();
+
+        let infos = self.ctx.analysed_fields()
+            .enumerate()
+            .map(|(i, (field, FieldContext { options, required, generic }))| {
+                let skipped = options.is_skipped();
+                let name = LitStr::new(&options.field_name(i, field), Span::call_site());
+                let ty = LitStr::new(&type_name(&field.ty), Span::call_site());
+                let doc = LitStr::new(&doc_text(&field.attrs), Span::call_site());
+                let attrs = cfg_attrs(field);
+
+                quote! {
+                    #(#attrs)*
+                    #field_info {
+                        name: #name,
+                        ty: #ty,
+                        required: #required,
+                        generic: #generic,
+                        skipped: #skipped,
+                        doc: #doc
+                    }
+                }
+            });
+
+        let field_info_struct: ItemStruct = parse_quote! {
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub struct #field_info {
+                pub name: &'static str,
+                pub ty: &'static str,
+                pub required: bool,
+                pub generic: bool,
+                pub skipped: bool,
+                pub doc: &'static str
+            }
+        };
+
+        // The identifiers of the fields with setters and whether they're required, which `flatten` checks its fields against
+        let flattened_fields = self.ctx.analysed_fields()
+            .filter(|(_, field_ctx)| field_ctx.options.has_setter())
+            .filter_map(|(field, field_ctx)| field.ident.as_ref().map(|ident| (field, field_ctx, ident)))
+            .map(|(field, FieldContext { required, .. }, ident)| {
+                let name = LitStr::new(&ident.unraw().to_string(), Span::call_site());
+                let attrs = cfg_attrs(field);
+
+                quote! {
+                    #(#attrs)*
+                    (#name, #required)
+                }
+            });
+
+        // Required fields with `setter(skip)` can't be set through `flatten`, so a struct with any can't be flattened
+        let unflattened_fields = self.ctx.analysed_fields()
+            .filter(|(_, field_ctx)| field_ctx.required && !field_ctx.options.has_setter())
+            .filter_map(|(field, _)| field.ident.as_ref().map(|ident| (field, ident)))
+            .map(|(field, ident)| {
+                let name = LitStr::new(&ident.unraw().to_string(), Span::call_site());
+                let attrs = cfg_attrs(field);
+
+                quote! {
+                    #(#attrs)*
+                    #name
+                }
+            });
+
+        let field_info_impl: ItemImpl = parse_quote! {
+            impl #field_info {
+                pub const FIELDS: &'static [Self] = &[#(#infos),*];
+                #[doc(hidden)]
+                pub const FLATTENED_FIELDS: &'static [(&'static str, bool)] = &[#(#flattened_fields),*];
+                #[doc(hidden)]
+                pub const UNFLATTENED_FIELDS: &'static [&'static str] = &[#(#unflattened_fields),*];
+                pub const REQUIRED_FIELD_COUNT: usize = #required_fields_count;
+                pub const OPTIONAL_FIELD_COUNT: usize = #optional_fields_count;
+                pub const GENERIC_REQUIRED_FIELD_COUNT: usize = #generic_required_fields_count;
+                pub const GENERIC_OPTIONAL_FIELD_COUNT: usize = #generic_optional_fields_count;
+                pub const SKIPPED_FIELD_COUNT: usize = #skipped_fields_count;
+            }
+        };
+
+        // The subject, params struct and builder all share the constants defined once on the field info struct
+        let consts = quote! {
+            pub const FIELDS: &'static [#field_info] = #field_info::FIELDS;
+            pub const REQUIRED_FIELD_COUNT: usize = #field_info::REQUIRED_FIELD_COUNT;
+            pub const OPTIONAL_FIELD_COUNT: usize = #field_info::OPTIONAL_FIELD_COUNT;
+            pub const GENERIC_REQUIRED_FIELD_COUNT: usize = #field_info::GENERIC_REQUIRED_FIELD_COUNT;
+            pub const GENERIC_OPTIONAL_FIELD_COUNT: usize = #field_info::GENERIC_OPTIONAL_FIELD_COUNT;
+            pub const SKIPPED_FIELD_COUNT: usize = #field_info::SKIPPED_FIELD_COUNT;
+        };
+
+        let include_params_generics = fields_metadata.generic_required_fields_count > 0;
+
+        let subject_impl: ItemImpl = parse_quote! {
+            impl #generics_def #subject #generics_expr #where_clause {
+                #consts
+            }
+        };
+        let params_impl: ItemImpl = if include_params_generics {
+            parse_quote! {
+                impl #generics_def #params #generics_expr #where_clause {
+                    #consts
+                }
+            }
+        } else {
+            parse_quote! {
+                impl #params {
+                    #consts
+                }
+            }
+        };
+        let builder_impl: ItemImpl = parse_quote! {
+            impl #generics_def #builder #generics_expr #where_clause {
+                #consts
+            }
+        };
+
+        field_info_struct.to_tokens(tokens);
+        field_info_impl.to_tokens(tokens);
+        subject_impl.to_tokens(tokens);
+        params_impl.to_tokens(tokens);
+        builder_impl.to_tokens(tokens);
+        tokens.extend(self.flatten_assertions());
+    }
+}
+
+impl<'a> ImplFieldMetadata<'a> {
+    /// Assertions that the fields repeated in each `flatten(..)` are the fields with setters of the nested struct,
+    /// checked against its `FLATTENED_FIELDS` when the constants are evaluated.
+    fn flatten_assertions(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        self.ctx.analysed_fields()
+            .filter_map(|(field, field_ctx)| field_ctx.options.flatten.as_ref().map(|flattened| (field, flattened)))
+            .filter_map(|(field, flattened)| Some((field, flattened, nested_field_info_path(&field.ty)?)))
+            .map(|(field, flattened, nested_field_info)| {
+                let span = field_span(field);
+                let attrs = cfg_attrs(field);
+                let field_name = field.ident.as_ref().map(|ident| ident.unraw().to_string()).unwrap_or_default();
+                let nested_name = type_name(&field.ty);
+                let count = flattened.len();
+
+                let listed = flattened
+                    .iter()
+                    .filter_map(|nested_field| nested_field.ident.as_ref().map(|ident| (nested_field, ident)))
+                    .map(|(nested_field, ident)| {
+                        let name = LitStr::new(&ident.unraw().to_string(), Span::call_site());
+                        let required = is_required(nested_field);
+                        let message = LitStr::new(&format!(
+                            "`{}: {}` in `flatten` of `{}` isn't {} field of `{}` with a setter",
+                            ident.unraw(),
+                            type_name(&nested_field.ty),
+                            field_name,
+                            if required { "a required" } else { "an optional" },
+                            nested_name
+                        ), Span::call_site());
+                        quote_spanned! {span=>
+                            ::core::assert!(lists(#nested_field_info::FLATTENED_FIELDS, #name, #required), #message);
+                        }
+                    });
+                let message = LitStr::new(
+                    &format!("`flatten` of `{}` doesn't list all the fields of `{}` with a setter", field_name, nested_name),
+                    Span::call_site()
+                );
+                let unflattened_message = LitStr::new(
+                    &format!("`{}` can't be flattened into `{}`, as it has required fields with `setter(skip)`", nested_name, field_name),
+                    Span::call_site()
+                );
+
+                quote_spanned! {span=>
+                    #(#attrs)*
+                    const _: () = {
+                        const fn lists(fields: &[(&str, bool)], name: &str, required: bool) -> bool {
+                            let mut i = 0;
+                            while i < fields.len() {
+                                let (field_name, field_required) = fields[i];
+                                let (a, b) = (field_name.as_bytes(), name.as_bytes());
+                                if a.len() == b.len() && field_required == required {
+                                    let mut j = 0;
+                                    while j < a.len() && a[j] == b[j] {
+                                        j += 1;
+                                    }
+                                    if j == a.len() {
+                                        return true;
+                                    }
+                                }
+                                i += 1;
+                            }
+                            false
+                        }
+
+                        ::core::assert!(#nested_field_info::UNFLATTENED_FIELDS.is_empty(), #unflattened_message);
+                        #(#listed)*
+                        ::core::assert!(#nested_field_info::FLATTENED_FIELDS.len() == #count, #message);
+                    };
+                }
+            })
+    }
+
+    /// The expressions of the field counts. Fields with `#[cfg]`s are only counted when their condition holds.
+    fn field_counts(&self) -> [Expr; 5] {
+        let generics = &self.ctx.generics.generics_def;
+        let (fixed_fields, cfg_fields) = self.ctx.analysed_fields()
+            .partition::<Vec<(&Field, &FieldContext)>, _>(|(field, _)| cfg_condition(field).is_none());
+        let fixed = FieldsMetadata::count(generics, fixed_fields);
+        let cfg_counts = cfg_fields
+            .iter()
+            .map(|field| (cfg_condition(field.0), FieldsMetadata::count(generics, [*field])))
+            .collect::<Vec<_>>();
+
+        let count = |f: fn(&FieldsMetadata) -> usize| -> Expr {
+            let fixed = f(&fixed);
+            let conditional = cfg_counts
+                .iter()
+                .filter(|(_, meta)| f(meta) > 0)
+                .map(|(condition, meta)| match f(meta) {
+                    1 => quote! { (::core::cfg!(#condition) as usize) },
+                    n => quote! { (::core::cfg!(#condition) as usize) * #n }
+                });
+            parse_quote! { #fixed #(+ #conditional)* }
+        };
+
+        [
+            count(|meta| meta.required_fields_count),
+            count(|meta| meta.optional_fields_count),
+            count(|meta| meta.generic_required_fields_count),
+            count(|meta| meta.generic_optional_fields_count),
+            count(|meta| meta.skipped_fields_count)
+        ]
+    }
+}
+
+/// The type as written, without the spaces a token stream puts between every token.
+pub fn type_name(ty: &Type) -> String {
+    tokens_text(ty)
+}
+
+/// The tokens as they'd be written, for generated docs and metadata.
+pub fn tokens_text(tokens: &impl ToTokens) -> String {
+    const REPLACEMENTS: [(&str, &str); 14] = [
+        (" <", "<"), ("< ", "<"), (" >", ">"), (" ::", "::"), (":: ", "::"), (" ,", ","), ("& ", "&"),
+        (" (", "("), ("( ", "("), (" )", ")"), ("[ ", "["), (" ]", "]"), (" . ", "."), (" !", "!")
+    ];
+
+    REPLACEMENTS
+        .iter()
+        .fold(tokens.to_token_stream().to_string(), |text, (from, to)| text.replace(from, to))
+}
+
+/// The text of the `///` doc comments in the attributes, one line per comment.
+pub fn doc_text(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                path,
+                value: Expr::Lit(ExprLit { lit: Lit::Str(doc), .. }),
+                ..
+            }) if path.is_ident("doc") => Some(doc.value()),
+            _ => None
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{doc_text, type_name, ImplFieldMetadata};
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_unit_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, Attribute, ItemStruct, Type};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct<T> {
+                /// The name.
+                pub name: String,
+                #[builder(name = "nickname")]
+                pub alias: Option<u8>,
+                pub value: T,
+                #[builder(skip)]
+                pub cached: u64,
+                #[builder(setter(skip))]
+                pub id: u32,
+                #[builder(setter(skip))]
+                pub secret: Option<u8>
+            }
+        };
+        let expected = quote! {
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::marker::Copy, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub struct MyStructFieldInfo {
+                pub name: &'static str,
+                pub ty: &'static str,
+                pub required: bool,
+                pub generic: bool,
+                pub skipped: bool,
+                pub doc: &'static str
+            }
+
+            impl MyStructFieldInfo {
+                pub const FIELDS: &'static [Self] = &[
+                    MyStructFieldInfo { name: "name", ty: "String", required: true, generic: false, skipped: false, doc: "The name." },
+                    MyStructFieldInfo { name: "nickname", ty: "Option<u8>", required: false, generic: false, skipped: false, doc: "" },
+                    MyStructFieldInfo { name: "value", ty: "T", required: true, generic: true, skipped: false, doc: "" },
+                    MyStructFieldInfo { name: "cached", ty: "u64", required: false, generic: false, skipped: true, doc: "" },
+                    MyStructFieldInfo { name: "id", ty: "u32", required: true, generic: false, skipped: false, doc: "" },
+                    MyStructFieldInfo { name: "secret", ty: "Option<u8>", required: false, generic: false, skipped: false, doc: "" }
+                ];
+                #[doc(hidden)]
+                pub const FLATTENED_FIELDS: &'static [(&'static str, bool)] = &[("name", true), ("alias", false), ("value", true)];
+                #[doc(hidden)]
+                pub const UNFLATTENED_FIELDS: &'static [&'static str] = &["id"];
+                pub const REQUIRED_FIELD_COUNT: usize = 2usize;
+                pub const OPTIONAL_FIELD_COUNT: usize = 2usize;
+                pub const GENERIC_REQUIRED_FIELD_COUNT: usize = 1usize;
+                pub const GENERIC_OPTIONAL_FIELD_COUNT: usize = 0usize;
+                pub const SKIPPED_FIELD_COUNT: usize = 1usize;
+            }
+
+            impl<T> MyStruct<T> {
+                pub const FIELDS: &'static [MyStructFieldInfo] = MyStructFieldInfo::FIELDS;
+                pub const REQUIRED_FIELD_COUNT: usize = MyStructFieldInfo::REQUIRED_FIELD_COUNT;
+                pub const OPTIONAL_FIELD_COUNT: usize = MyStructFieldInfo::OPTIONAL_FIELD_COUNT;
+                pub const GENERIC_REQUIRED_FIELD_COUNT: usize = MyStructFieldInfo::GENERIC_REQUIRED_FIELD_COUNT;
+                pub const GENERIC_OPTIONAL_FIELD_COUNT: usize = MyStructFieldInfo::GENERIC_OPTIONAL_FIELD_COUNT;
+                pub const SKIPPED_FIELD_COUNT: usize = MyStructFieldInfo::SKIPPED_FIELD_COUNT;
+            }
+
+            impl<T> MyStructParams<T> {
+                pub const FIELDS: &'static [MyStructFieldInfo] = MyStructFieldInfo::FIELDS;
+                pub const REQUIRED_FIELD_COUNT: usize = MyStructFieldInfo::REQUIRED_FIELD_COUNT;
+                pub const OPTIONAL_FIELD_COUNT: usize = MyStructFieldInfo::OPTIONAL_FIELD_COUNT;
+                pub const GENERIC_REQUIRED_FIELD_COUNT: usize = MyStructFieldInfo::GENERIC_REQUIRED_FIELD_COUNT;
+                pub const GENERIC_OPTIONAL_FIELD_COUNT: usize = MyStructFieldInfo::GENERIC_OPTIONAL_FIELD_COUNT;
+                pub const SKIPPED_FIELD_COUNT: usize = MyStructFieldInfo::SKIPPED_FIELD_COUNT;
+            }
+
+            impl<T> MyStructBuilder<T> {
+                pub const FIELDS: &'static [MyStructFieldInfo] = MyStructFieldInfo::FIELDS;
+                pub const REQUIRED_FIELD_COUNT: usize = MyStructFieldInfo::REQUIRED_FIELD_COUNT;
+                pub const OPTIONAL_FIELD_COUNT: usize = MyStructFieldInfo::OPTIONAL_FIELD_COUNT;
+                pub const GENERIC_REQUIRED_FIELD_COUNT: usize = MyStructFieldInfo::GENERIC_REQUIRED_FIELD_COUNT;
+                pub const GENERIC_OPTIONAL_FIELD_COUNT: usize = MyStructFieldInfo::GENERIC_OPTIONAL_FIELD_COUNT;
+                pub const SKIPPED_FIELD_COUNT: usize = MyStructFieldInfo::SKIPPED_FIELD_COUNT;
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_field_metadata = ImplFieldMetadata::from(&ctx);
+
+        assert_eq!(
+            impl_field_metadata.to_token_stream().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_flatten_assertions() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(flatten(created_by: String, note: Option<String>))]
+                pub audit: audit::AuditInfo
+            }
+        };
+        let expected = quote! {
+            const _: () = {
+                const fn lists(fields: &[(&str, bool)], name: &str, required: bool) -> bool {
+                    let mut i = 0;
+                    while i < fields.len() {
+                        let (field_name, field_required) = fields[i];
+                        let (a, b) = (field_name.as_bytes(), name.as_bytes());
+                        if a.len() == b.len() && field_required == required {
+                            let mut j = 0;
+                            while j < a.len() && a[j] == b[j] {
+                                j += 1;
+                            }
+                            if j == a.len() {
+                                return true;
+                            }
+                        }
+                        i += 1;
+                    }
+                    false
+                }
+
+                ::core::assert!(
+                    audit::AuditInfoFieldInfo::UNFLATTENED_FIELDS.is_empty(),
+                    "`audit::AuditInfo` can't be flattened into `audit`, as it has required fields with `setter(skip)`"
+                );
+                ::core::assert!(
+                    lists(audit::AuditInfoFieldInfo::FLATTENED_FIELDS, "created_by", true),
+                    "`created_by: String` in `flatten` of `audit` isn't a required field of `audit::AuditInfo` with a setter"
+                );
+                ::core::assert!(
+                    lists(audit::AuditInfoFieldInfo::FLATTENED_FIELDS, "note", false),
+                    "`note: Option<String>` in `flatten` of `audit` isn't an optional field of `audit::AuditInfo` with a setter"
+                );
+                ::core::assert!(
+                    audit::AuditInfoFieldInfo::FLATTENED_FIELDS.len() == 2usize,
+                    "`flatten` of `audit` doesn't list all the fields of `audit::AuditInfo` with a setter"
+                );
+            };
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_field_metadata = ImplFieldMetadata::from(&ctx);
+
+        assert_eq!(
+            impl_field_metadata.flatten_assertions().collect::<TokenStream>().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_unit_struct() {
+        let item_struct = sample_unit_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_field_metadata = ImplFieldMetadata::from(&ctx);
+
+        assert_eq!(
+            impl_field_metadata.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+
+    #[test]
+    fn test_type_name() {
+        let types: Vec<Type> = vec![
+            parse_quote! { std::option::Option<String> },
+            parse_quote! { &'a [u8] },
+            parse_quote! { Box<dyn Fn(u8, u16) -> u8> },
+            parse_quote! { (u8, HashMap<String, Vec<T>>) }
+        ];
+
+        let names = types.iter().map(type_name).collect::<Vec<_>>();
+
+        assert_eq!(names, vec![
+            "std::option::Option<String>",
+            "&'a [u8]",
+            "Box<dyn Fn(u8, u16) -> u8>",
+            "(u8, HashMap<String, Vec<T>>)"
+        ]);
+    }
+
+    #[test]
+    fn test_doc_text() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[doc = " The platypus' name."] },
+            parse_quote! { #[serde(rename = "platypusName")] },
+            parse_quote! { #[doc = "   Indented."] }
+        ];
+
+        assert_eq!(doc_text(&attrs), "The platypus' name.\n  Indented.");
+    }
+}
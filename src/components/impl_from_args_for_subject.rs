@@ -0,0 +1,179 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_condition};
+use crate::components::{allow_deprecated, args_fields};
+use crate::struct_builder::{BuilderContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, Field, Fields, ItemImpl};
+
+pub struct ImplFromArgsForSubject<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplFromArgsForSubject<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplFromArgsForSubject<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            params,
+            params_argument,
+            args,
+            options,
+            generics,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        if !options.clap || !matches!(self.fields, Fields::Named(_)) {
+            return;
+        }
+
+        let fields = args_fields(self.ctx);
+        let include_args_generics = fields.iter().any(|(_, field_ctx)| field_ctx.generic);
+
+        let required_values = fields
+            .iter()
+            .filter(|(_, field_ctx)| field_ctx.required)
+            .map(|(field, _)| {
+                let ident = field.ident.as_ref().expect("named field missing ident");
+                let attrs = cfg_attrs(field);
+                quote! { #(#attrs)* #ident: value.#ident }
+            })
+            .collect::<Vec<_>>();
+        let (optional_fields, cfg_optional_fields) = fields
+            .iter()
+            .filter(|(_, field_ctx)| !field_ctx.required)
+            .map(|(field, _)| *field)
+            .partition::<Vec<&Field>, _>(|field| cfg_condition(field).is_none());
+        let optional_setters = optional_fields
+            .iter()
+            .map(|field| {
+                let ident = field.ident.as_ref().expect("named field missing ident");
+                let fn_ident = format_ident!("with_{}", ident);
+                quote! { .#fn_ident(value.#ident) }
+            })
+            .collect::<Vec<_>>();
+
+        // Setters of fields with `#[cfg]`s can't be chained, so they're called in their own statements
+        let builder = quote! { Self::builder(#params_argument) #(#optional_setters)* };
+        let build = if cfg_optional_fields.is_empty() {
+            quote! { #builder.build() }
+        } else {
+            let stmts = cfg_optional_fields
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.as_ref().expect("named field missing ident");
+                    let fn_ident = format_ident!("with_{}", ident);
+                    let attrs = cfg_attrs(field);
+                    quote! { #(#attrs)* let builder = builder.#fn_ident(value.#ident); }
+                });
+            quote! {
+                let builder = #builder;
+                #(#stmts)*
+                builder.build()
+            }
+        };
+
+        let body = quote! {
+            let #params_argument = #params {
+                #(#required_values),*
+            };
+
+            #build
+        };
+
+        let allow_deprecated = allow_deprecated(self.fields);
+
+        let item_impl: ItemImpl = if include_args_generics {
+            parse_quote! {
+                #allow_deprecated
+                impl #generics_def ::core::convert::From<#args #generics_expr> for #subject #generics_expr #where_clause {
+                    fn from(value: #args #generics_expr) -> Self {
+                        #body
+                    }
+                }
+            }
+        } else {
+            parse_quote! {
+                #allow_deprecated
+                impl #generics_def ::core::convert::From<#args> for #subject #generics_expr #where_clause {
+                    fn from(value: #args) -> Self {
+                        #body
+                    }
+                }
+            }
+        };
+
+        item_impl.to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ImplFromArgsForSubject;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, ItemImpl, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(clap)]
+            pub struct MyStruct<T> {
+                pub name: String,
+                pub age: Option<u8>,
+                #[builder(skip)]
+                pub cached: u64,
+                #[builder(setter(skip))]
+                pub tag: T
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl<T> ::core::convert::From<MyStructArgs<T>> for MyStruct<T> {
+                fn from(value: MyStructArgs<T>) -> Self {
+                    let params = MyStructParams {
+                        name: value.name,
+                        tag: value.tag
+                    };
+
+                    Self::builder(params)
+                        .with_age(value.age)
+                        .build()
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_args_for_subject = ImplFromArgsForSubject::from(&ctx);
+
+        assert_eq!(
+            impl_from_args_for_subject.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_clap_option() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_args_for_subject = ImplFromArgsForSubject::from(&ctx);
+
+        assert_eq!(
+            impl_from_args_for_subject.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
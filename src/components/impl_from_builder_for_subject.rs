@@ -1,23 +1,22 @@
 use crate::struct_builder::{BuilderContext, GenericsContext};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{parse_quote, Fields, ItemImpl, ItemStruct};
+use syn::{parse_quote, Fields, ItemImpl};
 
-pub struct ImplFromBuilderForSubject {
-    ctx: BuilderContext,
+pub struct ImplFromBuilderForSubject<'a> {
+    ctx: &'a BuilderContext,
     unit: bool
 }
 
-impl From<&ItemStruct> for ImplFromBuilderForSubject {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let unit = matches!(&value.fields, Fields::Unit);
+impl<'a> From<&'a BuilderContext> for ImplFromBuilderForSubject<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        let unit = matches!(&ctx.fields, Fields::Unit);
 
         Self { ctx, unit }
     }
 }
 
-impl ToTokens for ImplFromBuilderForSubject {
+impl ToTokens for ImplFromBuilderForSubject<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext {
             subject,
@@ -29,12 +28,13 @@ impl ToTokens for ImplFromBuilderForSubject {
         let GenericsContext {
             generics_def,
             generics_expr,
-            where_clause
+            where_clause,
+            ..
         } = &generics;
 
         if !self.unit {
             let item_impl: ItemImpl = parse_quote! {
-                impl #generics_def From<#builder #generics_expr> for #subject #generics_expr #where_clause {
+                impl #generics_def ::core::convert::From<#builder #generics_expr> for #subject #generics_expr #where_clause {
                     fn from(value: #builder #generics_expr) -> Self {
                         value.#builder_subject_field
                     }
@@ -49,16 +49,17 @@ impl ToTokens for ImplFromBuilderForSubject {
 #[cfg(test)]
 mod tests {
     use crate::components::{ImplFromParamsForSubject, ImplFromBuilderForSubject};
+    use crate::struct_builder::BuilderContext;
     use crate::test_util::{sample_named_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
     use proc_macro2::TokenStream;
     use quote::ToTokens;
-    use syn::{parse_quote, ItemImpl};
+    use syn::{parse_quote, ItemImpl, ItemStruct};
 
     #[test]
     fn test_with_named_fields() {
         let item_struct = sample_named_item_struct();
         let expected: ItemImpl = parse_quote! {
-            impl<T, I: Send, W> From<MyStructBuilder<T, I, W>> for MyStruct<T, I, W>
+            impl<T, I: Send, W> ::core::convert::From<MyStructBuilder<T, I, W>> for MyStruct<T, I, W>
             where
                 W: Sync
             {
@@ -68,7 +69,8 @@ mod tests {
             }
         };
 
-        let impl_from_builder_for_subject = ImplFromBuilderForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_builder_for_subject = ImplFromBuilderForSubject::from(&ctx);
 
         assert_eq!(
             impl_from_builder_for_subject.to_token_stream().to_string(),
@@ -80,7 +82,7 @@ mod tests {
     fn test_with_unnamed_fields() {
         let item_struct = sample_unnamed_item_struct();
         let expected: ItemImpl = parse_quote! {
-            impl<T, I: Send, W> From<MyStructBuilder<T, I, W>> for MyStruct<T, I, W>
+            impl<T, I: Send, W> ::core::convert::From<MyStructBuilder<T, I, W>> for MyStruct<T, I, W>
             where
                 W: Sync
             {
@@ -90,7 +92,33 @@ mod tests {
             }
         };
 
-        let impl_from_builder_for_subject = ImplFromBuilderForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_builder_for_subject = ImplFromBuilderForSubject::from(&ctx);
+
+        assert_eq!(
+            impl_from_builder_for_subject.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_generic_defaults() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct<T: Clone = String, const N: usize = 4> {
+                pub value: T,
+                pub values: [u8; N]
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl<T: Clone, const N: usize> ::core::convert::From<MyStructBuilder<T, N>> for MyStruct<T, N> {
+                fn from(value: MyStructBuilder<T, N>) -> Self {
+                    value.inner
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_builder_for_subject = ImplFromBuilderForSubject::from(&ctx);
 
         assert_eq!(
             impl_from_builder_for_subject.to_token_stream().to_string(),
@@ -102,7 +130,8 @@ mod tests {
     fn test_with_unit_struct() {
         let item_struct = sample_unit_item_struct();
 
-        let subject_impl = ImplFromParamsForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplFromParamsForSubject::from(&ctx);
 
         assert_eq!(
             subject_impl.to_token_stream().to_string(),
@@ -1,27 +1,31 @@
+use crate::components::has_fallible_params;
 use crate::struct_builder::{BuilderContext, GenericsContext};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{parse_quote, Fields, ItemImpl, ItemStruct};
+use syn::{parse_quote, Fields, ItemImpl, Type};
 
-pub struct ImplFromParamsForSubject {
-    ctx: BuilderContext,
-    unit: bool
+pub struct ImplFromParamsForSubject<'a> {
+    ctx: &'a BuilderContext,
+    unit: bool,
+    fallible: bool
 }
 
-impl From<&ItemStruct> for ImplFromParamsForSubject {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let unit = matches!(&value.fields, Fields::Unit);
+impl<'a> From<&'a BuilderContext> for ImplFromParamsForSubject<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        let unit = matches!(&ctx.fields, Fields::Unit);
+        let fallible = has_fallible_params(ctx);
 
-        Self { ctx, unit }
+        Self { ctx, unit, fallible }
     }
 }
 
-impl ToTokens for ImplFromParamsForSubject {
+impl ToTokens for ImplFromParamsForSubject<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext {
             subject,
             params,
+            params_error,
+            builder,
             generics,
             fields_metadata,
             ..
@@ -29,15 +33,34 @@ impl ToTokens for ImplFromParamsForSubject {
         let GenericsContext {
             generics_def,
             generics_expr,
-            where_clause
+            where_clause,
+            ..
         } = &generics;
         
         let include_params_generics = fields_metadata.generic_required_fields_count > 0;
 
-        if !self.unit {
+        let params_type: Type = if include_params_generics {
+            parse_quote! { #params #generics_expr }
+        } else {
+            parse_quote! { #params }
+        };
+
+        if !self.unit && self.fallible {
+            let item_impl: ItemImpl = parse_quote! {
+                impl #generics_def ::core::convert::TryFrom<#params_type> for #subject #generics_expr #where_clause {
+                    type Error = #params_error;
+
+                    fn try_from(value: #params_type) -> ::core::result::Result<Self, Self::Error> {
+                        Self::builder(value).map(#builder::build)
+                    }
+                }
+            };
+
+            item_impl.to_tokens(tokens);
+        } else if !self.unit {
             let item_impl: ItemImpl = if include_params_generics {
                 parse_quote! {
-                    impl #generics_def From<#params #generics_expr> for #subject #generics_expr #where_clause {
+                    impl #generics_def ::core::convert::From<#params #generics_expr> for #subject #generics_expr #where_clause {
                         fn from(value: #params #generics_expr) -> Self {
                             Self::builder(value).build()
                         }
@@ -45,7 +68,7 @@ impl ToTokens for ImplFromParamsForSubject {
                 }
             } else {
                 parse_quote! {
-                    impl #generics_def From<#params> for #subject #generics_expr #where_clause {
+                    impl #generics_def ::core::convert::From<#params> for #subject #generics_expr #where_clause {
                         fn from(value: #params) -> Self {
                             Self::builder(value).build()
                         }
@@ -61,16 +84,17 @@ impl ToTokens for ImplFromParamsForSubject {
 #[cfg(test)]
 mod tests {
     use crate::components::ImplFromParamsForSubject;
+    use crate::struct_builder::BuilderContext;
     use crate::test_util::{sample_named_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
     use proc_macro2::TokenStream;
     use quote::ToTokens;
-    use syn::{parse_quote, ItemImpl};
+    use syn::{parse_quote, ItemImpl, ItemStruct};
 
     #[test]
     fn test_with_named_fields() {
         let item_struct = sample_named_item_struct();
         let expected: ItemImpl = parse_quote! {
-            impl<T, I: Send, W> From<MyStructParams<T, I, W>> for MyStruct<T, I, W>
+            impl<T, I: Send, W> ::core::convert::From<MyStructParams<T, I, W>> for MyStruct<T, I, W>
             where
                 W: Sync
             {
@@ -80,7 +104,8 @@ mod tests {
             }
         };
         
-        let impl_from_params_for_subject = ImplFromParamsForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_params_for_subject = ImplFromParamsForSubject::from(&ctx);
 
         assert_eq!(
             impl_from_params_for_subject.to_token_stream().to_string(),
@@ -92,7 +117,7 @@ mod tests {
     fn test_with_unnamed_fields() {
         let item_struct = sample_unnamed_item_struct();
         let expected: ItemImpl = parse_quote! {
-            impl<T, I: Send, W> From<MyStructParams<T, I, W>> for MyStruct<T, I, W>
+            impl<T, I: Send, W> ::core::convert::From<MyStructParams<T, I, W>> for MyStruct<T, I, W>
             where
                 W: Sync
             {
@@ -102,7 +127,8 @@ mod tests {
             }
         };
 
-        let impl_from_params_for_subject = ImplFromParamsForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_params_for_subject = ImplFromParamsForSubject::from(&ctx);
 
         assert_eq!(
             impl_from_params_for_subject.to_token_stream().to_string(),
@@ -110,11 +136,39 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_with_fallible_params() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(params_type = u32, try_convert)]
+                pub port: NonZeroU32
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl ::core::convert::TryFrom<MyStructParams> for MyStruct {
+                type Error = MyStructParamsError;
+
+                fn try_from(value: MyStructParams) -> ::core::result::Result<Self, Self::Error> {
+                    Self::builder(value).map(MyStructBuilder::build)
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_params_for_subject = ImplFromParamsForSubject::from(&ctx);
+
+        assert_eq!(
+            impl_from_params_for_subject.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
     #[test]
     fn test_with_unit_struct() {
         let item_struct = sample_unit_item_struct();
 
-        let subject_impl = ImplFromParamsForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplFromParamsForSubject::from(&ctx);
 
         assert_eq!(
             subject_impl.to_token_stream().to_string(),
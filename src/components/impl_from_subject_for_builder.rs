@@ -1,23 +1,22 @@
 use crate::struct_builder::{BuilderContext, GenericsContext};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{parse_quote, Fields, ItemImpl, ItemStruct};
+use syn::{parse_quote, Fields, ItemImpl};
 
-pub struct ImplFromSubjectForBuilder {
-    ctx: BuilderContext,
+pub struct ImplFromSubjectForBuilder<'a> {
+    ctx: &'a BuilderContext,
     unit: bool
 }
 
-impl From<&ItemStruct> for ImplFromSubjectForBuilder {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let unit = matches!(&value.fields, Fields::Unit);
+impl<'a> From<&'a BuilderContext> for ImplFromSubjectForBuilder<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        let unit = matches!(&ctx.fields, Fields::Unit);
 
         Self { ctx, unit }
     }
 }
 
-impl ToTokens for ImplFromSubjectForBuilder {
+impl ToTokens for ImplFromSubjectForBuilder<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext {
             subject,
@@ -29,12 +28,13 @@ impl ToTokens for ImplFromSubjectForBuilder {
         let GenericsContext {
             generics_def,
             generics_expr,
-            where_clause
+            where_clause,
+            ..
         } = &generics;
 
         if !self.unit {
             let item_impl: ItemImpl = parse_quote! {
-                impl #generics_def From<#subject #generics_expr> for #builder #generics_expr #where_clause {
+                impl #generics_def ::core::convert::From<#subject #generics_expr> for #builder #generics_expr #where_clause {
                     fn from(value: #subject #generics_expr) -> Self {
                         Self { #builder_subject_field: value }
                     }
@@ -49,6 +49,7 @@ impl ToTokens for ImplFromSubjectForBuilder {
 #[cfg(test)]
 mod tests {
     use crate::components::{ImplFromParamsForSubject, ImplFromSubjectForBuilder};
+    use crate::struct_builder::BuilderContext;
     use crate::test_util::{sample_named_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
     use proc_macro2::TokenStream;
     use quote::ToTokens;
@@ -58,7 +59,7 @@ mod tests {
     fn test_with_named_fields() {
         let item_struct = sample_named_item_struct();
         let expected: ItemImpl = parse_quote! {
-            impl<T, I: Send, W> From<MyStruct<T, I, W>> for MyStructBuilder<T, I, W>
+            impl<T, I: Send, W> ::core::convert::From<MyStruct<T, I, W>> for MyStructBuilder<T, I, W>
             where
                 W: Sync
             {
@@ -68,7 +69,8 @@ mod tests {
             }
         };
 
-        let impl_from_subject_for_builder = ImplFromSubjectForBuilder::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_subject_for_builder = ImplFromSubjectForBuilder::from(&ctx);
 
         assert_eq!(
             impl_from_subject_for_builder.to_token_stream().to_string(),
@@ -80,7 +82,7 @@ mod tests {
     fn test_with_unnamed_fields() {
         let item_struct = sample_unnamed_item_struct();
         let expected: ItemImpl = parse_quote! {
-            impl<T, I: Send, W> From<MyStruct<T, I, W>> for MyStructBuilder<T, I, W>
+            impl<T, I: Send, W> ::core::convert::From<MyStruct<T, I, W>> for MyStructBuilder<T, I, W>
             where
                 W: Sync
             {
@@ -90,7 +92,8 @@ mod tests {
             }
         };
 
-        let impl_from_subject_for_builder = ImplFromSubjectForBuilder::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_from_subject_for_builder = ImplFromSubjectForBuilder::from(&ctx);
 
         assert_eq!(
             impl_from_subject_for_builder.to_token_stream().to_string(),
@@ -102,7 +105,8 @@ mod tests {
     fn test_with_unit_struct() {
         let item_struct = sample_unit_item_struct();
 
-        let subject_impl = ImplFromParamsForSubject::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplFromParamsForSubject::from(&ctx);
 
         assert_eq!(
             subject_impl.to_token_stream().to_string(),
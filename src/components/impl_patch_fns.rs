@@ -0,0 +1,379 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_condition, cfg_expr, cfg_members, cfg_stmts, CfgMember};
+use crate::components::{allow_deprecated, field_span, patch_fields, patch_includes_generics};
+use crate::struct_builder::{BuilderContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, parse_quote_spanned, Attribute, Expr, Field, FieldValue, Fields, ImplItemFn, ItemImpl, Member, Path, Stmt, Token, Type, WherePredicate};
+
+pub struct ImplPatchFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplPatchFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplPatchFns<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            builder,
+            builder_subject_field,
+            patch,
+            options,
+            generics,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        if !options.patch || matches!(self.fields, Fields::Unit) {
+            return;
+        }
+
+        let fields = patch_fields(self.ctx);
+        let patch_field_list = fields.iter().map(|(_, _, field, _)| *field).collect::<Vec<&Field>>();
+        let subject_field_list = self.fields.iter().collect::<Vec<&Field>>();
+
+        // Fields with `#[cfg]`s can shift the members of tuple fields after them, in both the patch and the subject
+        let members = fields
+            .iter()
+            .enumerate()
+            .map(|(patch_index, (patch_member, subject_member, field, _))| {
+                let (patch_members, subject_members) = match subject_member {
+                    Member::Unnamed(subject_index) => (
+                        cfg_members(&patch_field_list, patch_index),
+                        cfg_members(&subject_field_list, subject_index.index as usize)
+                    ),
+                    Member::Named(_) => (
+                        vec![CfgMember { cfg: None, member: patch_member.clone() }],
+                        vec![CfgMember { cfg: None, member: subject_member.clone() }]
+                    )
+                };
+                (patch_member, patch_members, subject_members, *field)
+            })
+            .collect::<Vec<_>>();
+
+        let empty_values = members
+            .iter()
+            .map(|(patch_member, _, _, field)| {
+                let span = field_span(field);
+                (*patch_member, cfg_attrs(field), parse_quote_spanned! {span=> ::core::option::Option::None })
+            })
+            .collect::<Vec<(&Member, Vec<Attribute>, Expr)>>();
+
+        let merged_values = members
+            .iter()
+            .map(|(patch_member, patch_members, _, field)| {
+                let span = field_span(field);
+                let value = cfg_expr(patch_members, |member| parse_quote_spanned! {span=> other.#member.or(self.#member) });
+                (*patch_member, cfg_attrs(field), value)
+            })
+            .collect::<Vec<(&Member, Vec<Attribute>, Expr)>>();
+
+        let apply_stmts = members
+            .iter()
+            .flat_map(|(_, patch_members, subject_members, field)| {
+                let span = field_span(field);
+                let stmts = cfg_stmts(patch_members, |patch_member| cfg_stmts(subject_members, |subject_member| vec![
+                    parse_quote_spanned! {span=>
+                        if let ::core::option::Option::Some(value) = patch.#patch_member {
+                            self.#subject_member = value;
+                        }
+                    }
+                ]));
+                let attrs = cfg_attrs(field);
+                if attrs.is_empty() {
+                    stmts
+                } else {
+                    vec![parse_quote! { #(#attrs)* { #(#stmts)* } }]
+                }
+            })
+            .collect::<Vec<Stmt>>();
+
+        let diff_values = members
+            .iter()
+            .map(|(patch_member, _, subject_members, field)| {
+                let span = field_span(field);
+                let value = cfg_expr(subject_members, |member| parse_quote_spanned! {span=>
+                    if from.#member != to.#member {
+                        ::core::option::Option::Some(::core::clone::Clone::clone(&to.#member))
+                    } else {
+                        ::core::option::Option::None
+                    }
+                });
+                (*patch_member, cfg_attrs(field), value)
+            })
+            .collect::<Vec<(&Member, Vec<Attribute>, Expr)>>();
+
+        // Higher-ranked bounds aren't checked for concrete types until `diff` is used, so fields that can't be compared
+        // or cloned only make `diff` unavailable instead of failing to compile. Fields with `#[cfg]`s are left out, since
+        // their types may not exist.
+        let diff_predicates = fields
+            .iter()
+            .filter(|(_, _, field, _)| cfg_condition(field).is_none())
+            .map::<WherePredicate, _>(|(_, _, field, _)| {
+                let span = field_span(field);
+                let ty = &field.ty;
+                parse_quote_spanned! {span=> for<'__patch> #ty: ::core::cmp::PartialEq + ::core::clone::Clone }
+            })
+            .collect::<Punctuated<WherePredicate, Token![,]>>();
+
+        let include_patch_generics = patch_includes_generics(self.ctx);
+        let patch_type: Type = if include_patch_generics {
+            parse_quote! { #patch #generics_expr }
+        } else {
+            parse_quote! { #patch }
+        };
+        let patch_path: Path = if include_patch_generics {
+            parse_quote! { #patch :: #generics_expr }
+        } else {
+            parse_quote! { #patch }
+        };
+
+        let empty = construct(&parse_quote! { Self }, empty_values);
+        let merged = construct(&parse_quote! { Self }, merged_values);
+        let diff = construct(&patch_path, diff_values);
+
+        let patch_fns: Vec<ImplItemFn> = vec![
+            parse_quote! {
+                pub fn empty() -> Self {
+                    #empty
+                }
+            },
+            parse_quote! {
+                pub fn merge(self, other: Self) -> Self {
+                    #merged
+                }
+            }
+        ];
+
+        let allow_deprecated = allow_deprecated(self.fields);
+
+        let patch_impl: ItemImpl = if include_patch_generics {
+            parse_quote! {
+                #allow_deprecated
+                impl #generics_def #patch_type #where_clause {
+                    #(#patch_fns)*
+                }
+            }
+        } else {
+            parse_quote! {
+                #allow_deprecated
+                impl #patch_type {
+                    #(#patch_fns)*
+                }
+            }
+        };
+
+        let subject_impl: ItemImpl = parse_quote! {
+            #allow_deprecated
+            impl #generics_def #subject #generics_expr #where_clause {
+                pub fn apply(&mut self, patch: #patch_type) {
+                    #(#apply_stmts)*
+                }
+
+                pub fn diff(from: &Self, to: &Self) -> #patch_type
+                where
+                    #diff_predicates
+                {
+                    #diff
+                }
+            }
+        };
+
+        let builder_impl: ItemImpl = parse_quote! {
+            impl #generics_def #builder #generics_expr #where_clause {
+                pub fn apply(mut self, patch: #patch_type) -> Self {
+                    self.#builder_subject_field.apply(patch);
+                    self
+                }
+            }
+        };
+
+        patch_impl.to_tokens(tokens);
+        subject_impl.to_tokens(tokens);
+        builder_impl.to_tokens(tokens);
+    }
+}
+
+/// Constructs a struct from its members' values, positionally for tuple structs so `#[cfg]`s on the values can shift
+/// the fields after them.
+fn construct(path: &Path, values: Vec<(&Member, Vec<Attribute>, Expr)>) -> Expr {
+    if values.iter().all(|(member, _, _)| matches!(member, Member::Named(_))) {
+        let values = values
+            .into_iter()
+            .map(|(member, attrs, expr)| FieldValue {
+                attrs,
+                member: member.clone(),
+                colon_token: Some(Default::default()),
+                expr
+            })
+            .collect::<Punctuated<FieldValue, Token![,]>>();
+        parse_quote! { #path { #values } }
+    } else {
+        let values = values.into_iter().map(|(_, attrs, expr)| quote! { #(#attrs)* #expr });
+        parse_quote! { #path(#(#values),*) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ImplPatchFns;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(patch)]
+            pub struct MyStruct<T> {
+                pub name: Option<String>,
+                pub value: T,
+                #[builder(skip)]
+                pub cached: u64
+            }
+        };
+        let expected = quote! {
+            impl<T> MyStructPatch<T> {
+                pub fn empty() -> Self {
+                    Self {
+                        name: ::core::option::Option::None,
+                        value: ::core::option::Option::None
+                    }
+                }
+
+                pub fn merge(self, other: Self) -> Self {
+                    Self {
+                        name: other.name.or(self.name),
+                        value: other.value.or(self.value)
+                    }
+                }
+            }
+
+            impl<T> MyStruct<T> {
+                pub fn apply(&mut self, patch: MyStructPatch<T>) {
+                    if let ::core::option::Option::Some(value) = patch.name {
+                        self.name = value;
+                    }
+                    if let ::core::option::Option::Some(value) = patch.value {
+                        self.value = value;
+                    }
+                }
+
+                pub fn diff(from: &Self, to: &Self) -> MyStructPatch<T>
+                where
+                    for<'__patch> Option<String>: ::core::cmp::PartialEq + ::core::clone::Clone,
+                    for<'__patch> T: ::core::cmp::PartialEq + ::core::clone::Clone
+                {
+                    MyStructPatch::<T> {
+                        name: if from.name != to.name {
+                            ::core::option::Option::Some(::core::clone::Clone::clone(&to.name))
+                        } else {
+                            ::core::option::Option::None
+                        },
+                        value: if from.value != to.value {
+                            ::core::option::Option::Some(::core::clone::Clone::clone(&to.value))
+                        } else {
+                            ::core::option::Option::None
+                        }
+                    }
+                }
+            }
+
+            impl<T> MyStructBuilder<T> {
+                pub fn apply(mut self, patch: MyStructPatch<T>) -> Self {
+                    self.inner.apply(patch);
+                    self
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_patch_fns = ImplPatchFns::from(&ctx);
+
+        assert_eq!(
+            impl_patch_fns.to_token_stream().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_unnamed_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(patch)]
+            pub struct MyStruct(#[builder(skip)] u64, pub String);
+        };
+        let expected = quote! {
+            impl MyStructPatch {
+                pub fn empty() -> Self {
+                    Self(::core::option::Option::None)
+                }
+
+                pub fn merge(self, other: Self) -> Self {
+                    Self(other.0.or(self.0))
+                }
+            }
+
+            impl MyStruct {
+                pub fn apply(&mut self, patch: MyStructPatch) {
+                    if let ::core::option::Option::Some(value) = patch.0 {
+                        self.1 = value;
+                    }
+                }
+
+                pub fn diff(from: &Self, to: &Self) -> MyStructPatch
+                where
+                    for<'__patch> String: ::core::cmp::PartialEq + ::core::clone::Clone
+                {
+                    MyStructPatch(
+                        if from.1 != to.1 {
+                            ::core::option::Option::Some(::core::clone::Clone::clone(&to.1))
+                        } else {
+                            ::core::option::Option::None
+                        }
+                    )
+                }
+            }
+
+            impl MyStructBuilder {
+                pub fn apply(mut self, patch: MyStructPatch) -> Self {
+                    self.inner.apply(patch);
+                    self
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_patch_fns = ImplPatchFns::from(&ctx);
+
+        assert_eq!(
+            impl_patch_fns.to_token_stream().to_string(),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_patch_option() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_patch_fns = ImplPatchFns::from(&ctx);
+
+        assert_eq!(
+            impl_patch_fns.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
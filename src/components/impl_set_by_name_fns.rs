@@ -0,0 +1,380 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_members, cfg_stmts, CfgMember};
+use crate::components::{allow_deprecated, field_span, from_str_type, needs_std};
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, parse_quote_spanned, Arm, Expr, Field, Fields, ItemEnum, ItemImpl, LitStr, Member, Token, WherePredicate};
+
+pub struct ImplSetByNameFns<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for ImplSetByNameFns<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for ImplSetByNameFns<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            builder,
+            builder_subject_field,
+            set_field_error,
+            alloc,
+            std,
+            options,
+            generics,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        if !options.set_by_name || matches!(self.fields, Fields::Unit) {
+            return;
+        }
+
+        let named_fields = self.ctx.analysed_fields()
+            .enumerate()
+            .filter(|(_, (_, field_ctx))| field_ctx.options.is_set_by_name())
+            .map(|(i, (field, field_ctx))| {
+                let member = match &field.ident {
+                    Some(ident) => Member::from(ident.clone()),
+                    None => Member::from(i)
+                };
+                let name = LitStr::new(&field_ctx.options.field_name(i, field), Span::call_site());
+                (member, name, field, field_ctx)
+            })
+            .collect::<Vec<(Member, LitStr, &Field, &FieldContext)>>();
+
+        let names = named_fields.iter().map(|(_, name, field, _)| {
+            let attrs = cfg_attrs(field);
+            quote! { #(#attrs)* #name }
+        });
+
+        // Generic fields need their parsed type bounded, other fields are checked where they're parsed
+        let predicates = named_fields
+            .iter()
+            .filter(|(_, _, _, field_ctx)| field_ctx.generic)
+            .flat_map::<[WherePredicate; 2], _>(|(_, _, field, _)| {
+                let span = field_span(field);
+                let ty = from_str_type(field);
+                [
+                    parse_quote_spanned! {span=> #ty: ::core::str::FromStr },
+                    parse_quote_spanned! {span=> <#ty as ::core::str::FromStr>::Err: ::core::fmt::Display }
+                ]
+            })
+            .collect::<Punctuated<WherePredicate, Token![,]>>();
+
+        let subject_fields = self.fields.iter().collect::<Vec<&Field>>();
+        let arms = named_fields
+            .iter()
+            .map::<Arm, _>(|(member, name, field, field_ctx)| {
+                let span = field_span(field);
+                let ty = from_str_type(field);
+                let parsed: Expr = parse_quote_spanned! {span=> #set_field_error::parse::<#ty>(#name, value)? };
+                let value: Expr = if field_ctx.required {
+                    parsed
+                } else {
+                    parse_quote_spanned! {span=> ::core::option::Option::Some(#parsed) }
+                };
+
+                let attrs = cfg_attrs(field);
+
+                // Fields with `#[cfg]`s before a tuple field shift its index
+                let members = match member {
+                    Member::Unnamed(index) => cfg_members(&subject_fields, index.index as usize),
+                    Member::Named(_) => vec![CfgMember { cfg: None, member: member.clone() }]
+                };
+                if let [CfgMember { cfg: None, member }] = members.as_slice() {
+                    return parse_quote_spanned! {span=>
+                        #(#attrs)*
+                        #name => self.#builder_subject_field.#member = #value,
+                    };
+                }
+
+                let stmts = cfg_stmts(&members, |member| vec![parse_quote_spanned! {span=>
+                    self.#builder_subject_field.#member = #value;
+                }]);
+                parse_quote_spanned! {span=>
+                    #(#attrs)*
+                    #name => {
+                        #(#stmts)*
+                    }
+                }
+            })
+            .collect::<Vec<Arm>>();
+
+        let error_enum: ItemEnum = parse_quote! {
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub enum #set_field_error {
+                UnknownField {
+                    field: #alloc::string::String,
+                    suggestion: ::core::option::Option<&'static str>
+                },
+                InvalidValue {
+                    field: &'static str,
+                    value: #alloc::string::String,
+                    message: #alloc::string::String
+                }
+            }
+        };
+
+        let mut error_impls: Vec<ItemImpl> = vec![
+            parse_quote! {
+                impl #set_field_error {
+                    const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+
+                    fn parse<T>(field: &'static str, value: &str) -> ::core::result::Result<T, Self>
+                    where
+                        T: ::core::str::FromStr,
+                        <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                    {
+                        T::from_str(value).map_err(|error| Self::InvalidValue {
+                            field,
+                            value: #alloc::string::ToString::to_string(value),
+                            message: #alloc::string::ToString::to_string(&error)
+                        })
+                    }
+
+                    fn unknown_field(field: &str) -> Self {
+                        // Levenshtein distance, to suggest a known field name close to the unknown one
+                        fn distance(a: &str, b: &str) -> usize {
+                            let b = b.chars().collect::<#alloc::vec::Vec<char>>();
+                            let mut previous = (0..=b.len()).collect::<#alloc::vec::Vec<usize>>();
+                            for (i, a) in a.chars().enumerate() {
+                                let mut current = #alloc::vec![i + 1];
+                                for (j, b) in b.iter().enumerate() {
+                                    let substitution = previous[j] + if a == *b { 0 } else { 1 };
+                                    current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+                                }
+                                previous = current;
+                            }
+                            previous[b.len()]
+                        }
+
+                        let suggestion = Self::FIELD_NAMES
+                            .iter()
+                            .map(|name| (distance(field, name), *name))
+                            .filter(|(distance, _)| *distance <= 2)
+                            .min_by_key(|(distance, _)| *distance)
+                            .map(|(_, name)| name);
+
+                        Self::UnknownField {
+                            field: #alloc::string::ToString::to_string(field),
+                            suggestion
+                        }
+                    }
+                }
+            },
+            parse_quote! {
+                impl ::core::fmt::Display for #set_field_error {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match self {
+                            Self::UnknownField { field, suggestion: ::core::option::Option::Some(suggestion) } =>
+                                ::core::write!(f, "unknown field `{}`, did you mean `{}`?", field, suggestion),
+                            Self::UnknownField { field, suggestion: ::core::option::Option::None } =>
+                                ::core::write!(f, "unknown field `{}`", field),
+                            Self::InvalidValue { field, value, message } =>
+                                ::core::write!(f, "invalid value `{}` for field `{}`: {}", value, field, message)
+                        }
+                    }
+                }
+            }
+        ];
+
+        // `core::error::Error` needs Rust 1.81, so only `std` builds get the impl, through `std::error::Error`
+        if needs_std(self.ctx) {
+            error_impls.push(parse_quote! {
+                impl #std::error::Error for #set_field_error {}
+            });
+        }
+
+        let allow_deprecated = allow_deprecated(self.fields);
+        let builder_impl: ItemImpl = parse_quote! {
+            #allow_deprecated
+            impl #generics_def #builder #generics_expr #where_clause {
+                pub fn set_by_name(&mut self, field: &str, value: &str) -> ::core::result::Result<(), #set_field_error>
+                where
+                    #predicates
+                {
+                    match field {
+                        #(#arms)*
+                        _ => return ::core::result::Result::Err(#set_field_error::unknown_field(field))
+                    }
+                    ::core::result::Result::Ok(())
+                }
+
+                pub fn apply_pairs<__P, __K, __V>(&mut self, pairs: __P) -> ::core::result::Result<(), #set_field_error>
+                where
+                    __P: ::core::iter::IntoIterator<Item = (__K, __V)>,
+                    __K: ::core::convert::AsRef<str>,
+                    __V: ::core::convert::AsRef<str>,
+                    #predicates
+                {
+                    for (field, value) in pairs {
+                        self.set_by_name(field.as_ref(), value.as_ref())?;
+                    }
+                    ::core::result::Result::Ok(())
+                }
+            }
+        };
+
+        error_enum.to_tokens(tokens);
+        error_impls.iter().for_each(|item_impl| item_impl.to_tokens(tokens));
+        builder_impl.to_tokens(tokens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ImplSetByNameFns;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, File, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(set_by_name)]
+            pub struct MyStruct<T> {
+                pub value: T,
+                #[builder(name = "first-name")]
+                pub first_name: Option<String>,
+                #[builder(skip)]
+                pub cached: u64
+            }
+        };
+        let expected: File = parse_quote! {
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub enum MyStructSetFieldError {
+                UnknownField {
+                    field: __MyStruct_alloc::string::String,
+                    suggestion: ::core::option::Option<&'static str>
+                },
+                InvalidValue {
+                    field: &'static str,
+                    value: __MyStruct_alloc::string::String,
+                    message: __MyStruct_alloc::string::String
+                }
+            }
+
+            impl MyStructSetFieldError {
+                const FIELD_NAMES: &'static [&'static str] = &["value", "first-name"];
+
+                fn parse<T>(field: &'static str, value: &str) -> ::core::result::Result<T, Self>
+                where
+                    T: ::core::str::FromStr,
+                    <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                {
+                    T::from_str(value).map_err(|error| Self::InvalidValue {
+                        field,
+                        value: __MyStruct_alloc::string::ToString::to_string(value),
+                        message: __MyStruct_alloc::string::ToString::to_string(&error)
+                    })
+                }
+
+                fn unknown_field(field: &str) -> Self {
+                    fn distance(a: &str, b: &str) -> usize {
+                        let b = b.chars().collect::<__MyStruct_alloc::vec::Vec<char>>();
+                        let mut previous = (0..=b.len()).collect::<__MyStruct_alloc::vec::Vec<usize>>();
+                        for (i, a) in a.chars().enumerate() {
+                            let mut current = __MyStruct_alloc::vec![i + 1];
+                            for (j, b) in b.iter().enumerate() {
+                                let substitution = previous[j] + if a == *b { 0 } else { 1 };
+                                current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+                            }
+                            previous = current;
+                        }
+                        previous[b.len()]
+                    }
+
+                    let suggestion = Self::FIELD_NAMES
+                        .iter()
+                        .map(|name| (distance(field, name), *name))
+                        .filter(|(distance, _)| *distance <= 2)
+                        .min_by_key(|(distance, _)| *distance)
+                        .map(|(_, name)| name);
+
+                    Self::UnknownField {
+                        field: __MyStruct_alloc::string::ToString::to_string(field),
+                        suggestion
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for MyStructSetFieldError {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        Self::UnknownField { field, suggestion: ::core::option::Option::Some(suggestion) } =>
+                            ::core::write!(f, "unknown field `{}`, did you mean `{}`?", field, suggestion),
+                        Self::UnknownField { field, suggestion: ::core::option::Option::None } =>
+                            ::core::write!(f, "unknown field `{}`", field),
+                        Self::InvalidValue { field, value, message } =>
+                            ::core::write!(f, "invalid value `{}` for field `{}`: {}", value, field, message)
+                    }
+                }
+            }
+
+            impl __MyStruct_std::error::Error for MyStructSetFieldError {}
+
+            impl<T> MyStructBuilder<T> {
+                pub fn set_by_name(&mut self, field: &str, value: &str) -> ::core::result::Result<(), MyStructSetFieldError>
+                where
+                    T: ::core::str::FromStr,
+                    <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                {
+                    match field {
+                        "value" => self.inner.value = MyStructSetFieldError::parse::<T>("value", value)?,
+                        "first-name" => self.inner.first_name = ::core::option::Option::Some(MyStructSetFieldError::parse::<String>("first-name", value)?),
+                        _ => return ::core::result::Result::Err(MyStructSetFieldError::unknown_field(field))
+                    }
+                    ::core::result::Result::Ok(())
+                }
+
+                pub fn apply_pairs<__P, __K, __V>(&mut self, pairs: __P) -> ::core::result::Result<(), MyStructSetFieldError>
+                where
+                    __P: ::core::iter::IntoIterator<Item = (__K, __V)>,
+                    __K: ::core::convert::AsRef<str>,
+                    __V: ::core::convert::AsRef<str>,
+                    T: ::core::str::FromStr,
+                    <T as ::core::str::FromStr>::Err: ::core::fmt::Display
+                {
+                    for (field, value) in pairs {
+                        self.set_by_name(field.as_ref(), value.as_ref())?;
+                    }
+                    ::core::result::Result::Ok(())
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_set_by_name_fns = ImplSetByNameFns::from(&ctx);
+
+        assert_eq!(
+            impl_set_by_name_fns.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_set_by_name_option() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_set_by_name_fns = ImplSetByNameFns::from(&ctx);
+
+        assert_eq!(
+            impl_set_by_name_fns.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
@@ -1,47 +1,95 @@
+use crate::cfg_resolution::{cfg_attrs, cfg_expr, cfg_members};
+use crate::components::{allow_deprecated, field_span, has_fallible_params, nested_params_path, profile_fields_doc, profile_params_fields, required_fields_doc};
+use crate::generic_resolution::field_has_generic;
+use crate::options::{Convert, FieldOptions, ProfileOptions};
 use crate::struct_builder::{BuilderContext, GenericsContext};
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use std::fmt::Display;
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
-use syn::{parse_quote, Expr, Field, FieldValue, Fields, Index, ItemImpl, ItemStruct, Token, Type};
+use syn::{parse_quote, parse_quote_spanned, Expr, Field, FieldValue, Fields, GenericArgument, Ident, ItemImpl, LitStr, PathArguments, Token, Type};
 
-pub struct ImplSubjectFnBuilder {
-    ctx: BuilderContext,
-    fields: Fields
+pub struct ImplSubjectFnBuilder<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields,
+    profile: Option<&'a ProfileOptions>
 }
 
-impl From<&ItemStruct> for ImplSubjectFnBuilder {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let fields = value.fields.clone();
-        
-        Self { ctx, fields }
+impl<'a> From<&'a BuilderContext> for ImplSubjectFnBuilder<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields, profile: None }
+    }
+}
+
+impl<'a> ImplSubjectFnBuilder<'a> {
+    /// The builder function of a profile, taking the profile's params struct.
+    pub fn for_profile(ctx: &'a BuilderContext, profile: &'a ProfileOptions) -> Self {
+        Self { profile: Some(profile), ..Self::from(ctx) }
     }
 }
 
-impl ToTokens for ImplSubjectFnBuilder {
+impl ToTokens for ImplSubjectFnBuilder<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext { 
             subject,
-            params,
+            params_error,
             params_argument,
             builder,
             builder_subject_field,
+            options,
             generics,
             fields_metadata,
             ..
         } = &self.ctx;
-        
+        // A params struct with a field that needs dropping can't be taken apart in a `const fn`
+        let params_drop = self.ctx.field_contexts
+            .iter()
+            .any(|field_ctx| field_ctx.required && !field_ctx.options.has_const_setter(true));
+        let constness: Option<Token![const]> = (options.const_fn && !params_drop).then(Default::default);
+
+
         let optional_expr: Option<Expr> = match &self.fields {
             Fields::Named(named_fields) => {
                 let punctuated_fields = named_fields.named
                     .iter()
-                    .map::<FieldValue, _>(|field| {
+                    .zip(&self.ctx.field_contexts)
+                    .map::<FieldValue, _>(|(field, field_ctx)| {
                         let field_ident = field.ident.as_ref().expect("named field missing ident");
-                        if is_required(&field) {
-                            parse_quote! { #field_ident: #params_argument.#field_ident }
+                        let field_options = &field_ctx.options;
+                        let span = field_span(field);
+                        let mut field_value: FieldValue = if let Some(skip) = &field_options.skip {
+                            let value = skip.value();
+                            parse_quote_spanned! {span=> #field_ident: #value }
+                        } else if let Some(profile) = &self.profile {
+                            // Optional fields a profile requires are given unwrapped
+                            if !profile.requires(field) {
+                                parse_quote_spanned! {span=> #field_ident: ::core::option::Option::None }
+                            } else if field_ctx.required {
+                                let value = params_value(field_ident.unraw(), field_options, parse_quote_spanned! {span=> #params_argument.#field_ident }, params_error);
+                                parse_quote_spanned! {span=> #field_ident: #value }
+                            } else {
+                                parse_quote_spanned! {span=> #field_ident: ::core::option::Option::Some(#params_argument.#field_ident) }
+                            }
+                        } else if let (Some(flattened), Some(nested_params)) = (&field_options.flatten, nested_params_path(&field.ty)) {
+                            let field_type = &field.ty;
+                            let nested_idents = flattened
+                                .iter()
+                                .filter(|nested_field| is_required(nested_field))
+                                .map(|nested_field| nested_field.ident.as_ref().expect("named field missing ident"));
+                            parse_quote_spanned! {span=>
+                                #field_ident: ::core::convert::Into::<#field_type>::into(#nested_params {
+                                    #(#nested_idents: #params_argument.#nested_idents),*
+                                })
+                            }
+                        } else if field_ctx.required {
+                            let value = params_value(field_ident.unraw(), field_options, parse_quote_spanned! {span=> #params_argument.#field_ident }, params_error);
+                            parse_quote_spanned! {span=> #field_ident: #value }
                         } else {
-                            parse_quote! { #field_ident: ::std::option::Option::None }
-                        }
+                            parse_quote_spanned! {span=> #field_ident: ::core::option::Option::None }
+                        };
+                        field_value.attrs = cfg_attrs(field);
+                        field_value
                     })
                     .collect::<Punctuated<FieldValue, Token![,]>>();
                 
@@ -49,21 +97,32 @@ impl ToTokens for ImplSubjectFnBuilder {
             },
             
             Fields::Unnamed(unnamed_fields) => {
+                let params_fields = self.ctx.params_fields.iter().collect::<Vec<&Field>>();
                 let mut next_index = 0;
-                let punctuated_fields = unnamed_fields.unnamed
+                let values = unnamed_fields.unnamed
                     .iter()
-                    .map::<Expr, _>(|field|
-                        if is_required(&field) {
-                            let index = Index::from(next_index);
+                    .zip(&self.ctx.field_contexts)
+                    .enumerate()
+                    .map(|(i, (field, field_ctx))| {
+                        let field_options = &field_ctx.options;
+                        let span = field_span(field);
+                        let attrs = cfg_attrs(field);
+                        let value: Expr = if let Some(skip) = &field_options.skip {
+                            skip.value()
+                        } else if field_ctx.required {
+                            // Params fields with `#[cfg]`s before this one shift its index
+                            let members = cfg_members(&params_fields, next_index);
                             next_index += 1;
-                            parse_quote! { #params_argument.#index }
+                            cfg_expr(&members, |member| {
+                                params_value(i, field_options, parse_quote_spanned! {span=> #params_argument.#member }, params_error)
+                            })
                         } else {
-                            parse_quote! { ::std::option::Option::None }
-                        }
-                    )
-                    .collect::<Punctuated<Expr, Token![,]>>();
+                            parse_quote_spanned! {span=> ::core::option::Option::None }
+                        };
+                        quote! { #(#attrs)* #value }
+                    });
                 
-                Some(parse_quote! { Self(#punctuated_fields) })
+                Some(parse_quote! { Self(#(#values),*) })
             },
             
             Fields::Unit => None
@@ -73,25 +132,59 @@ impl ToTokens for ImplSubjectFnBuilder {
             let GenericsContext {
                 generics_def,
                 generics_expr,
-                where_clause
+                where_clause,
+                ..
             } = &generics;
 
-            let include_params_generics = fields_metadata.generic_required_fields_count > 0;
-            
-            let item_impl: ItemImpl = if include_params_generics {
+            let (params, builder_fn, include_params_generics, required) = match &self.profile {
+                Some(profile) => (
+                    profile.params(subject),
+                    profile.builder_fn(),
+                    profile_params_fields(self.ctx, profile).iter().any(|field| field_has_generic(generics_def, field)),
+                    profile_fields_doc(self.fields, profile)
+                ),
+                None => (
+                    self.ctx.params.clone(),
+                    format_ident!("builder"),
+                    fields_metadata.generic_required_fields_count > 0,
+                    required_fields_doc(self.ctx)
+                )
+            };
+            let params_type: Type = if include_params_generics {
+                parse_quote! { #params #generics_expr }
+            } else {
+                parse_quote! { #params }
+            };
+
+            let summary = format!(" Creates a [`{}`] from the required fields of [`{}`], given as a [`{}`].", builder, subject, params);
+            let required = format!(" {}", required);
+            let allow_deprecated = allow_deprecated(self.fields);
+
+            let mut item_impl: ItemImpl = if has_fallible_params(self.ctx) {
+                let errors = format!(" Fails with a [`{}`] if a field can't be converted from its params value.", params_error);
                 parse_quote! {
+                    #allow_deprecated
                     impl #generics_def #subject #generics_expr #where_clause {
-                        pub fn builder(#params_argument: #params #generics_expr) -> #builder #generics_expr {
-                            #builder {
+                        #[doc = #summary]
+                        #[doc = ""]
+                        #[doc = #required]
+                        #[doc = ""]
+                        #[doc = #errors]
+                        pub fn #builder_fn(#params_argument: #params_type) -> ::core::result::Result<#builder #generics_expr, #params_error> {
+                            ::core::result::Result::Ok(#builder {
                                 #builder_subject_field: #expr
-                            }
+                            })
                         }
                     }
                 }
             } else {
                 parse_quote! {
+                    #allow_deprecated
                     impl #generics_def #subject #generics_expr #where_clause {
-                        pub fn builder(#params_argument: #params) -> #builder #generics_expr {
+                        #[doc = #summary]
+                        #[doc = ""]
+                        #[doc = #required]
+                        pub #constness fn #builder_fn(#params_argument: #params_type) -> #builder #generics_expr {
                             #builder {
                                 #builder_subject_field: #expr
                             }
@@ -100,7 +193,33 @@ impl ToTokens for ImplSubjectFnBuilder {
                 }
             };
 
-            item_impl.to_tokens(tokens);   
+            // `From` can't be implemented as const, so const builders get an inherent alternative
+            if constness.is_some() && self.profile.is_none() {
+                item_impl.items.push(parse_quote! {
+                    pub const fn from_params(#params_argument: #params_type) -> Self {
+                        #expr
+                    }
+                });
+            }
+
+            item_impl.to_tokens(tokens);
+        }
+    }
+}
+
+/// The value of a required field from its params struct value, converted if the field has a `params_type`. A conversion
+/// that can fail returns early with the params error naming the field.
+pub fn params_value(name: impl Display, options: &FieldOptions, value: Expr, params_error: &Ident) -> Expr {
+    if options.params_type.is_none() {
+        return value;
+    }
+
+    match &options.convert {
+        None => parse_quote! { ::core::convert::Into::into(#value) },
+        Some(Convert::Infallible(convert)) => parse_quote! { #convert(#value) },
+        Some(Convert::Fallible(convert)) => {
+            let name = LitStr::new(&name.to_string(), Span::call_site());
+            parse_quote! { #convert(#value).map_err(|error| #params_error::new(#name, error))? }
         }
     }
 }
@@ -116,13 +235,40 @@ pub fn is_required(field: &Field) -> bool {
     }
 }
 
+/// The type wrapped by an [Option] field type, if the field is optional.
+pub fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path_type) = ty else {
+        return None;
+    };
+    let segment = path_type.path.segments.last().filter(|seg| seg.ident == "Option")?;
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first() {
+        Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+        _ => None
+    }
+}
+
+/// The type a field's value is parsed as with [FromStr](std::str::FromStr), which is the wrapped type for optional fields.
+pub fn from_str_type(field: &Field) -> &Type {
+    if is_required(field) {
+        &field.ty
+    } else {
+        option_inner_type(&field.ty).unwrap_or(&field.ty)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::components::impl_subject_fn_builder::ImplSubjectFnBuilder;
-    use crate::test_util::{sample_named_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
+    use crate::components::impl_subject_fn_builder::{option_inner_type, ImplSubjectFnBuilder};
+    use crate::options::StructOptions;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::{sample_named_item_struct, sample_skipped_named_item_struct, sample_skipped_unnamed_item_struct, sample_unit_item_struct, sample_unnamed_item_struct};
     use proc_macro2::TokenStream;
     use quote::ToTokens;
-    use syn::{parse_quote, ItemImpl};
+    use syn::{parse_quote, ItemImpl, ItemStruct, Type};
 
     #[test]
     fn test_with_named_fields() {
@@ -132,14 +278,17 @@ mod tests {
             where
                 W: Sync
             {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `public_field`, `private_field`, `dynamic`, `dynamic2`, `generic`, `generic_inline`, `generic_where`."]
                 pub fn builder(params: MyStructParams<T, I, W>) -> MyStructBuilder<T, I, W> {
                     MyStructBuilder {
                         inner: Self {
                             public_field: params.public_field,
                             private_field: params.private_field,
-                            optional: ::std::option::Option::None,
-                            test: ::std::option::Option::None,
-                            test2: ::std::option::Option::None,
+                            optional: ::core::option::Option::None,
+                            test: ::core::option::Option::None,
+                            test2: ::core::option::Option::None,
                             dynamic: params.dynamic,
                             dynamic2: params.dynamic2,
                             generic: params.generic,
@@ -151,7 +300,8 @@ mod tests {
             }
         };
         
-        let impl_subject_fn_builder = ImplSubjectFnBuilder::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let impl_subject_fn_builder = ImplSubjectFnBuilder::from(&ctx);
 
         assert_eq!(
             impl_subject_fn_builder.to_token_stream().to_string(),
@@ -167,14 +317,17 @@ mod tests {
             where
                 W: Sync
             {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `0`, `1`, `5`, `6`, `7`, `8`, `9`."]
                 pub fn builder(params: MyStructParams<T, I, W>) -> MyStructBuilder<T, I, W> {
                     MyStructBuilder {
                         inner: Self(
                             params.0,
                             params.1,
-                            ::std::option::Option::None,
-                            ::std::option::Option::None,
-                            ::std::option::Option::None,
+                            ::core::option::Option::None,
+                            ::core::option::Option::None,
+                            ::core::option::Option::None,
                             params.2,
                             params.3,
                             params.4,
@@ -186,7 +339,8 @@ mod tests {
             }
         };
 
-        let subject_impl = ImplSubjectFnBuilder::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
 
         assert_eq!(
             subject_impl.to_token_stream().to_string(),
@@ -194,11 +348,284 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_with_skipped_named_fields() {
+        let item_struct = sample_skipped_named_item_struct();
+        let expected: ItemImpl = parse_quote! {
+            impl<T> MyStruct<T> {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `required`."]
+                pub fn builder(params: MyStructParams) -> MyStructBuilder<T> {
+                    MyStructBuilder {
+                        inner: Self {
+                            required: params.required,
+                            cached: ::core::default::Default::default(),
+                            computed: vec![1, 2],
+                            optional: ::core::option::Option::None,
+                            marker: ::core::marker::PhantomData
+                        }
+                    }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_skipped_unnamed_fields() {
+        let item_struct = sample_skipped_unnamed_item_struct();
+        let expected: ItemImpl = parse_quote! {
+            impl MyStruct {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `1`, `4`."]
+                pub fn builder(params: MyStructParams) -> MyStructBuilder {
+                    MyStructBuilder {
+                        inner: Self(
+                            ::core::default::Default::default(),
+                            params.0,
+                            ::core::option::Option::None,
+                            3,
+                            params.1
+                        )
+                    }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_flattened_field() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub customer_id: u64,
+                #[builder(flatten(created_by: String, note: Option<String>))]
+                pub audit: AuditInfo,
+                pub comment: Option<String>
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStruct {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `customer_id`, `created_by`."]
+                pub fn builder(params: MyStructParams) -> MyStructBuilder {
+                    MyStructBuilder {
+                        inner: Self {
+                            customer_id: params.customer_id,
+                            audit: ::core::convert::Into::<AuditInfo>::into(AuditInfoParams {
+                                created_by: params.created_by
+                            }),
+                            comment: ::core::option::Option::None
+                        }
+                    }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_profile() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(profile(name = "self_signup", required(email, invite_code)))]
+            pub struct User<T> {
+                /// The user's email.
+                pub email: String,
+                pub role: Option<T>,
+                pub invite_code: Option<String>,
+                #[builder(skip)]
+                pub cached: u64
+            }
+        };
+        let profile = StructOptions::parse(&item_struct.attrs).unwrap().profiles.remove(0);
+        let expected: ItemImpl = parse_quote! {
+            impl<T> User<T> {
+                #[doc = " Creates a [`UserBuilder`] from the required fields of [`User`], given as a [`UserSelfSignupParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `email`, `invite_code`."]
+                pub fn self_signup_builder(params: UserSelfSignupParams) -> UserBuilder<T> {
+                    UserBuilder {
+                        inner: Self {
+                            email: params.email,
+                            role: ::core::option::Option::None,
+                            invite_code: ::core::option::Option::Some(params.invite_code),
+                            cached: ::core::default::Default::default()
+                        }
+                    }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::for_profile(&ctx, &profile);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_params_types() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct Server {
+                #[builder(params_type = &'static str)]
+                pub host: String,
+                #[builder(params_type = u32, try_convert)]
+                pub port: NonZeroU32,
+                #[builder(params_type = String, convert = PathBuf::from)]
+                pub root: PathBuf,
+                #[builder(params_type = i64, try_convert = Timeout::try_from_secs)]
+                pub timeout: Timeout
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl Server {
+                #[doc = " Creates a [`ServerBuilder`] from the required fields of [`Server`], given as a [`ServerParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `host`, `port`, `root`, `timeout`."]
+                #[doc = ""]
+                #[doc = " Fails with a [`ServerParamsError`] if a field can't be converted from its params value."]
+                pub fn builder(params: ServerParams) -> ::core::result::Result<ServerBuilder, ServerParamsError> {
+                    ::core::result::Result::Ok(ServerBuilder {
+                        inner: Self {
+                            host: ::core::convert::Into::into(params.host),
+                            port: ::core::convert::TryFrom::try_from(params.port).map_err(|error| ServerParamsError::new("port", error))?,
+                            root: PathBuf::from(params.root),
+                            timeout: Timeout::try_from_secs(params.timeout).map_err(|error| ServerParamsError::new("timeout", error))?
+                        }
+                    })
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_const() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(const)]
+            pub struct MyStruct {
+                pub port: u16,
+                pub workers: Option<u8>
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStruct {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `port`."]
+                pub const fn builder(params: MyStructParams) -> MyStructBuilder {
+                    MyStructBuilder {
+                        inner: Self {
+                            port: params.port,
+                            workers: ::core::option::Option::None
+                        }
+                    }
+                }
+
+                pub const fn from_params(params: MyStructParams) -> Self {
+                    Self {
+                        port: params.port,
+                        workers: ::core::option::Option::None
+                    }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_const_and_non_const_required_field() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(const)]
+            pub struct MyStruct {
+                #[builder(setter(const = false))]
+                pub host: String,
+                pub workers: Option<u8>
+            }
+        };
+        let expected: ItemImpl = parse_quote! {
+            impl MyStruct {
+                #[doc = " Creates a [`MyStructBuilder`] from the required fields of [`MyStruct`], given as a [`MyStructParams`]."]
+                #[doc = ""]
+                #[doc = " Required fields: `host`."]
+                pub fn builder(params: MyStructParams) -> MyStructBuilder {
+                    MyStructBuilder {
+                        inner: Self {
+                            host: params.host,
+                            workers: ::core::option::Option::None
+                        }
+                    }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
+
+        assert_eq!(
+            subject_impl.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_option_inner_type() {
+        let optional: Type = parse_quote! { ::core::option::Option<Vec<u8>> };
+        let required: Type = parse_quote! { Box<Option<u8>> };
+
+        assert_eq!(
+            option_inner_type(&optional).map(|ty| ty.to_token_stream().to_string()),
+            Some("Vec < u8 >".to_owned())
+        );
+        assert!(option_inner_type(&required).is_none());
+    }
+
     #[test]
     fn test_with_unit_struct() {
         let item_struct = sample_unit_item_struct();
         
-        let subject_impl = ImplSubjectFnBuilder::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let subject_impl = ImplSubjectFnBuilder::from(&ctx);
         
         assert_eq!(
             subject_impl.to_token_stream().to_string(),
@@ -1,15 +1,41 @@
 mod impl_subject_fn_builder;
 mod params_struct;
+mod params_error_struct;
 mod builder_struct;
 mod impl_builder_fns;
+mod impl_builder_update_fns;
+mod impl_builder_nested_fns;
+mod impl_builder_preset_fns;
 mod impl_from_builder_for_subject;
 mod impl_from_params_for_subject;
 mod impl_from_subject_for_builder;
+mod patch_struct;
+mod impl_patch_fns;
+mod impl_env_fns;
+mod args_struct;
+mod impl_from_args_for_subject;
+mod impl_set_by_name_fns;
+mod impl_field_metadata;
+mod extern_crate_alloc;
+mod extern_crate_std;
 
 pub use impl_subject_fn_builder::*;
 pub use params_struct::*;
+pub use params_error_struct::*;
 pub use builder_struct::*;
 pub use impl_builder_fns::*;
+pub use impl_builder_update_fns::*;
+pub use impl_builder_nested_fns::*;
+pub use impl_builder_preset_fns::*;
 pub use impl_from_builder_for_subject::*;
 pub use impl_from_params_for_subject::*;
 pub use impl_from_subject_for_builder::*;
+pub use patch_struct::*;
+pub use impl_patch_fns::*;
+pub use impl_env_fns::*;
+pub use args_struct::*;
+pub use impl_from_args_for_subject::*;
+pub use impl_set_by_name_fns::*;
+pub use impl_field_metadata::*;
+pub use extern_crate_alloc::*;
+pub use extern_crate_std::*;
@@ -0,0 +1,144 @@
+use crate::components::needs_std;
+use crate::struct_builder::BuilderContext;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{parse_quote, ItemImpl, ItemStruct};
+
+pub struct ParamsErrorStruct<'a> {
+    ctx: &'a BuilderContext
+}
+
+impl<'a> From<&'a BuilderContext> for ParamsErrorStruct<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx }
+    }
+}
+
+impl ToTokens for ParamsErrorStruct<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            subject,
+            params,
+            params_error,
+            alloc,
+            std,
+            ..
+        } = &self.ctx;
+
+        if !has_fallible_params(self.ctx) {
+            return;
+        }
+
+        let doc = format!(" The error of a field of [`{}`] that can't be converted from its [`{}`] value.", subject, params);
+
+        let error_struct: ItemStruct = parse_quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub struct #params_error {
+                pub field: &'static str,
+                pub message: #alloc::string::String
+            }
+        };
+
+        let mut error_impls: Vec<ItemImpl> = vec![
+            parse_quote! {
+                impl #params_error {
+                    fn new(field: &'static str, error: impl ::core::fmt::Display) -> Self {
+                        Self {
+                            field,
+                            message: #alloc::string::ToString::to_string(&error)
+                        }
+                    }
+                }
+            },
+            parse_quote! {
+                impl ::core::fmt::Display for #params_error {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        ::core::write!(f, "invalid value for field `{}`: {}", self.field, self.message)
+                    }
+                }
+            }
+        ];
+
+        // `core::error::Error` needs Rust 1.81, so only `std` builds get the impl, through `std::error::Error`
+        if needs_std(self.ctx) {
+            error_impls.push(parse_quote! {
+                impl #std::error::Error for #params_error {}
+            });
+        }
+
+        error_struct.to_tokens(tokens);
+        error_impls.iter().for_each(|item_impl| item_impl.to_tokens(tokens));
+    }
+}
+
+/// Whether any field's params struct value goes through a conversion that can fail, which makes creating the builder fail.
+pub fn has_fallible_params(ctx: &BuilderContext) -> bool {
+    ctx.field_contexts.iter().any(|field_ctx| field_ctx.options.is_fallible())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::ParamsErrorStruct;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::sample_named_item_struct;
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, File, ItemStruct};
+
+    #[test]
+    fn test_with_fallible_field() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(params_type = u32, try_convert)]
+                pub port: NonZeroU32
+            }
+        };
+        let expected: File = parse_quote! {
+            #[doc = " The error of a field of [`MyStruct`] that can't be converted from its [`MyStructParams`] value."]
+            #[derive(::core::fmt::Debug, ::core::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            pub struct MyStructParamsError {
+                pub field: &'static str,
+                pub message: __MyStruct_alloc::string::String
+            }
+
+            impl MyStructParamsError {
+                fn new(field: &'static str, error: impl ::core::fmt::Display) -> Self {
+                    Self {
+                        field,
+                        message: __MyStruct_alloc::string::ToString::to_string(&error)
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for MyStructParamsError {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::write!(f, "invalid value for field `{}`: {}", self.field, self.message)
+                }
+            }
+
+            impl __MyStruct_std::error::Error for MyStructParamsError {}
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_error_struct = ParamsErrorStruct::from(&ctx);
+
+        assert_eq!(
+            params_error_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_fallible_fields() {
+        let item_struct = sample_named_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_error_struct = ParamsErrorStruct::from(&ctx);
+
+        assert_eq!(
+            params_error_struct.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
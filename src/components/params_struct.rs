@@ -1,54 +1,76 @@
-use crate::struct_builder::{BuilderContext, GenericsContext};
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
 use proc_macro2::TokenStream;
-use quote::ToTokens;
-use syn::{parse_quote, Field, Fields, ItemStruct, Token};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Attribute, Expr, Field, Fields, Ident, ImplItemFn, ItemImpl, ItemStruct, Token, Visibility};
 use syn::punctuated::Punctuated;
-use crate::components::is_required;
+use crate::cfg_resolution::cfg_attrs;
+use crate::components::{is_required, option_inner_type};
+use crate::generic_resolution::field_has_generic;
+use crate::options::{is_builder_attribute, ProfileOptions};
 
-pub struct ParamsStruct {
-    ctx: BuilderContext,
-    fields: Fields
+pub struct ParamsStruct<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields,
+    profile: Option<&'a ProfileOptions>
 }
 
-impl From<&ItemStruct> for ParamsStruct {
-    fn from(value: &ItemStruct) -> Self {
-        let ctx: BuilderContext = value.into();
-        let fields = value.fields.clone();
-
-        Self { ctx, fields }
+impl<'a> From<&'a BuilderContext> for ParamsStruct<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields, profile: None }
     }
 }
 
-impl ToTokens for ParamsStruct {
+impl ToTokens for ParamsStruct<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let BuilderContext {
-            params,
+            subject,
+            builder,
             generics,
-            fields_metadata,
             attributes,
             ..
         } = &self.ctx;
         let GenericsContext {
+            generics_decl,
             generics_def,
             where_clause,
             ..
         } = &generics;
-        let attrs = &attributes.outer_attrs;
-        
-        let include_generics = fields_metadata.generic_required_fields_count > 0;
+        // The subject's docs are replaced by docs about the params struct
+        let attrs = attributes.outer_attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+        let (params, builder_fn, required) = match &self.profile {
+            Some(profile) => (profile.params(subject), profile.builder_fn(), profile_fields_doc(self.fields, profile)),
+            None => (self.ctx.params.clone(), format_ident!("builder"), required_fields_doc(self.ctx))
+        };
+        let summary = format!(" The required fields of [`{}`], to create a [`{}`] with [`{}::{}`].", subject, builder, subject, builder_fn);
+        let required = format!(" {}", required);
+
+        let punctuated_fields = self.punctuated_fields();
+        let include_generics = match &self.profile {
+            Some(_) => punctuated_fields.iter().any(|field| field_has_generic(generics_def, field)),
+            None => self.ctx.fields_metadata.generic_required_fields_count > 0
+        };
         
         match &self.fields {
             Fields::Named(_) => {
-                let punctuated_fields = self.punctuated_fields();
                 let item_struct: ItemStruct = if include_generics {
                     parse_quote! {
+                        #[doc = #summary]
+                        #[doc = ""]
+                        #[doc = #required]
                         #(#attrs)*
-                        pub struct #params #generics_def #where_clause {
+                        pub struct #params #generics_decl #where_clause {
                             #punctuated_fields
                         }
                     }
                 } else {
                     parse_quote! {
+                        #[doc = #summary]
+                        #[doc = ""]
+                        #[doc = #required]
                         #(#attrs)*
                         pub struct #params {
                             #punctuated_fields
@@ -60,14 +82,19 @@ impl ToTokens for ParamsStruct {
             },
             
             Fields::Unnamed(_) => {
-                let punctuated_fields = self.punctuated_fields();
                 let item_struct: ItemStruct = if include_generics {
                     parse_quote! {
+                        #[doc = #summary]
+                        #[doc = ""]
+                        #[doc = #required]
                         #(#attrs)*
-                        pub struct #params #generics_def ( #punctuated_fields ) #where_clause;
+                        pub struct #params #generics_decl ( #punctuated_fields ) #where_clause;
                     }
                 } else {
                     parse_quote! {
+                        #[doc = #summary]
+                        #[doc = ""]
+                        #[doc = #required]
                         #(#attrs)*
                         pub struct #params ( #punctuated_fields );
                     }
@@ -76,40 +103,201 @@ impl ToTokens for ParamsStruct {
                 item_struct.to_tokens(tokens);
             },
             
-            Fields::Unit => ()
+            Fields::Unit => return
+        }
+
+        // Non-exhaustive subjects are meant to be built from other crates, so they get a constructor for the params
+        if attributes.non_exhaustive {
+            self.constructor(&params, include_generics).to_tokens(tokens);
         }
     }
 }
 
-impl ParamsStruct {
-    fn punctuated_fields(&self) -> Punctuated<Field, Token![,]> {
-        self.fields
+impl<'a> ParamsStruct<'a> {
+    /// The params struct of a profile, with its own required fields.
+    pub fn for_profile(ctx: &'a BuilderContext, profile: &'a ProfileOptions) -> Self {
+        Self { profile: Some(profile), ..Self::from(ctx) }
+    }
+
+    fn constructor(&self, params: &Ident, include_generics: bool) -> ItemImpl {
+        let BuilderContext { subject, generics, .. } = &self.ctx;
+        let GenericsContext {
+            generics_def,
+            generics_expr,
+            where_clause,
+            ..
+        } = &generics;
+
+        let fields = self.punctuated_fields();
+        let arguments = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| field.ident.clone().unwrap_or_else(|| format_ident!("field_{}", i)))
+            .collect::<Vec<Ident>>();
+        let attrs = fields.iter().map(cfg_attrs).collect::<Vec<Vec<Attribute>>>();
+        let types = fields.iter().map(|field| &field.ty);
+        let inputs = arguments
+            .iter()
+            .zip(&attrs)
+            .zip(types)
+            .map(|((argument, attrs), ty)| quote! { #(#attrs)* #argument: #ty });
+        let values = arguments
             .iter()
-            .filter(|f| is_required(f))
-            .cloned()
+            .zip(&attrs)
+            .map(|(argument, attrs)| quote! { #(#attrs)* #argument });
+        let value: Expr = match &self.fields {
+            Fields::Named(_) => parse_quote! { Self { #(#values),* } },
+            _ => parse_quote! { Self(#(#values),*) }
+        };
+        let doc = format!(" Creates a [`{}`] from the required fields of [`{}`], in order.", params, subject);
+
+        let constructor: ImplItemFn = parse_quote! {
+            #[doc = #doc]
+            pub fn new(#(#inputs),*) -> Self {
+                #value
+            }
+        };
+
+        if include_generics {
+            parse_quote! {
+                impl #generics_def #params #generics_expr #where_clause {
+                    #constructor
+                }
+            }
+        } else {
+            parse_quote! {
+                impl #params {
+                    #constructor
+                }
+            }
+        }
+    }
+
+    fn punctuated_fields(&self) -> Punctuated<Field, Token![,]> {
+        let fields = match &self.profile {
+            Some(profile) => profile_params_fields(self.ctx, profile),
+            None => self.ctx.params_fields.clone()
+        };
+
+        fields
+            .into_iter()
+            .map(|mut f| {
+                f.attrs.retain(|attr| !is_builder_attribute(attr));
+                f
+            })
             .collect::<Punctuated<Field, Token![,]>>()
     }
 }
 
+/// The params struct's fields: the required fields that aren't skipped, with their `params_type` if any, and a flattened
+/// field's own required fields in its place. Fields have the given visibility unless they have their own.
+pub fn params_fields<'a>(fields: impl IntoIterator<Item = (&'a Field, &'a FieldContext)>, vis: &Visibility) -> Vec<Field> {
+    fields
+        .into_iter()
+        .filter(|(_, field_ctx)| field_ctx.required)
+        .flat_map(|(field, FieldContext { options, .. })| {
+            let vis = options.params_field_vis(vis);
+            match &options.flatten {
+                Some(flattened) => flattened
+                    .iter()
+                    .filter(|nested_field| is_required(nested_field))
+                    .cloned()
+                    .map(|mut nested_field| {
+                        nested_field.vis = vis.clone();
+                        nested_field.attrs.extend(cfg_attrs(field));
+                        nested_field
+                    })
+                    .collect(),
+                None => {
+                    let mut field = options.params_field(field);
+                    field.vis = vis;
+                    vec![field]
+                }
+            }
+        })
+        .collect()
+}
+
+/// A profile's params struct fields: the fields it requires, with `Option` fields taking the wrapped type.
+pub fn profile_params_fields(ctx: &BuilderContext, profile: &ProfileOptions) -> Vec<Field> {
+    let vis = ctx.options.params_field_vis();
+    ctx.analysed_fields()
+        .filter(|(field, _)| profile.requires(field))
+        .map(|(field, FieldContext { options, .. })| {
+            let mut field = options.params_field(field);
+            field.vis = options.params_field_vis(&vis);
+            if let Some(inner_type) = option_inner_type(&field.ty) {
+                field.ty = inner_type.clone();
+            }
+            field
+        })
+        .collect()
+}
+
+/// Lists a profile's required fields, like [required_fields_doc].
+pub fn profile_fields_doc(fields: &Fields, profile: &ProfileOptions) -> String {
+    let names = fields
+        .iter()
+        .filter(|field| profile.requires(field))
+        .filter_map(|field| field.ident.as_ref())
+        .map(|ident| format!("`{}`", ident.unraw()))
+        .collect::<Vec<String>>();
+
+    if names.is_empty() {
+        "There are no required fields.".to_owned()
+    } else {
+        format!("Required fields: {}.", names.join(", "))
+    }
+}
+
+/// Lists the struct's required fields by name (or index), for the docs of the items that take them.
+pub fn required_fields_doc(ctx: &BuilderContext) -> String {
+    let names = ctx.analysed_fields()
+        .enumerate()
+        .filter(|(_, (_, field_ctx))| field_ctx.required)
+        .flat_map(|(i, (field, field_ctx))| match (&field_ctx.options.flatten, &field.ident) {
+            (Some(flattened), _) => flattened
+                .iter()
+                .filter(|nested_field| is_required(nested_field))
+                .filter_map(|nested_field| nested_field.ident.as_ref())
+                .map(|ident| format!("`{}`", ident.unraw()))
+                .collect(),
+            (None, Some(ident)) => vec![format!("`{}`", ident.unraw())],
+            (None, None) => vec![format!("`{}`", i)]
+        })
+        .collect::<Vec<String>>();
+
+    if names.is_empty() {
+        "There are no required fields.".to_owned()
+    } else {
+        format!("Required fields: {}.", names.join(", "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proc_macro2::TokenStream;
     use quote::ToTokens;
-    use syn::{parse_quote, ItemStruct};
+    use syn::{parse_quote, File, ItemStruct};
     use crate::components::params_struct::ParamsStruct;
-    use crate::test_util::{sample_named_item_struct, sample_unnamed_item_struct};
+    use crate::options::StructOptions;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::{sample_named_item_struct, sample_skipped_named_item_struct, sample_skipped_unnamed_item_struct, sample_unnamed_item_struct};
 
     #[test]
     fn test_with_named_fields() { 
         let item_struct = sample_named_item_struct();
         let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `public_field`, `private_field`, `dynamic`, `dynamic2`, `generic`, `generic_inline`, `generic_where`."]
             #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
             pub struct MyStructParams<T, I: Send, W>
             where
                 W: Sync
             {
                 pub public_field: String,
-                private_field: String,
+                pub private_field: String,
                 pub dynamic: Box<dyn Send>,
                 pub dynamic2: Box<Option<dyn Send>>,
                 #[serde(rename = "simpleGeneric")]
@@ -119,7 +307,8 @@ mod tests {
             }
         };
         
-        let params_struct = ParamsStruct::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
 
         assert_eq!(
             params_struct.to_token_stream().to_string(),
@@ -131,9 +320,12 @@ mod tests {
     fn test_with_unnamed_fields() {
         let item_struct = sample_unnamed_item_struct();
         let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `0`, `1`, `5`, `6`, `7`, `8`, `9`."]
             pub struct MyStructParams<T, I: Send, W>(
                 pub String,
-                String,
+                pub String,
                 pub Box<dyn Send>,
                 pub Box<Option<dyn Send>>,
                 #[inline_required]
@@ -145,7 +337,8 @@ mod tests {
                 W: Sync;
         };
 
-        let params_struct = ParamsStruct::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
 
         assert_eq!(
             params_struct.to_token_stream().to_string(),
@@ -153,11 +346,274 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_with_skipped_named_fields() {
+        let item_struct = sample_skipped_named_item_struct();
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `required`."]
+            pub struct MyStructParams {
+                pub required: String
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_skipped_unnamed_fields() {
+        let item_struct = sample_skipped_unnamed_item_struct();
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `1`, `4`."]
+            pub struct MyStructParams(pub String, pub String);
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_docs() {
+        let item_struct: ItemStruct = parse_quote! {
+            /// A platypus.
+            #[derive(Debug)]
+            pub struct MyStruct {
+                /// The platypus' name.
+                pub name: String,
+                pub age: Option<u8>
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `name`."]
+            #[derive(Debug)]
+            pub struct MyStructParams {
+                /// The platypus' name.
+                pub name: String
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_params_field_vis() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(params_field_vis = "pub(crate)")]
+            pub struct MyStruct {
+                name: String,
+                #[builder(params_field_vis = "pub")]
+                pub id: u64,
+                #[builder(flatten(created_by: String), params_field_vis = "pub(super)")]
+                audit: AuditInfo
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `name`, `id`, `created_by`."]
+            pub struct MyStructParams {
+                pub(crate) name: String,
+                pub id: u64,
+                pub(super) created_by: String
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_non_exhaustive() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[non_exhaustive]
+            #[derive(Debug)]
+            pub struct MyStruct<T> {
+                pub name: String,
+                pub value: T,
+                pub age: Option<u8>
+            }
+        };
+        let expected: File = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `name`, `value`."]
+            #[derive(Debug)]
+            pub struct MyStructParams<T> {
+                pub name: String,
+                pub value: T
+            }
+
+            impl<T> MyStructParams<T> {
+                #[doc = " Creates a [`MyStructParams`] from the required fields of [`MyStruct`], in order."]
+                pub fn new(name: String, value: T) -> Self {
+                    Self { name, value }
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_non_exhaustive_unnamed_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[non_exhaustive]
+            pub struct MyStruct(pub String, pub Option<u8>, pub u16);
+        };
+        let expected: File = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `0`, `2`."]
+            pub struct MyStructParams(pub String, pub u16);
+
+            impl MyStructParams {
+                #[doc = " Creates a [`MyStructParams`] from the required fields of [`MyStruct`], in order."]
+                pub fn new(field_0: String, field_1: u16) -> Self {
+                    Self(field_0, field_1)
+                }
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_flattened_field() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub customer_id: u64,
+                #[builder(flatten(created_by: String, note: Option<String>))]
+                pub audit: AuditInfo,
+                pub comment: Option<String>
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`MyStruct`], to create a [`MyStructBuilder`] with [`MyStruct::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `customer_id`, `created_by`."]
+            pub struct MyStructParams {
+                pub customer_id: u64,
+                pub created_by: String
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_params_types() {
+        let item_struct: ItemStruct = parse_quote! {
+            pub struct Server {
+                #[builder(params_type = &'static str)]
+                pub host: String,
+                #[builder(params_type = u32, try_convert)]
+                pub port: NonZeroU32
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`Server`], to create a [`ServerBuilder`] with [`Server::builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `host`, `port`."]
+            pub struct ServerParams {
+                pub host: &'static str,
+                pub port: u32
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_profile() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(profile(name = "self_signup", required(email, invite_code)))]
+            pub struct User<T> {
+                /// The user's email.
+                pub email: String,
+                pub role: Option<T>,
+                pub invite_code: Option<String>,
+                #[builder(skip)]
+                pub cached: u64
+            }
+        };
+        let profile = StructOptions::parse(&item_struct.attrs).unwrap().profiles.remove(0);
+        let expected: ItemStruct = parse_quote! {
+            #[doc = " The required fields of [`User`], to create a [`UserBuilder`] with [`User::self_signup_builder`]."]
+            #[doc = ""]
+            #[doc = " Required fields: `email`, `invite_code`."]
+            pub struct UserSelfSignupParams {
+                /// The user's email.
+                pub email: String,
+                pub invite_code: String
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::for_profile(&ctx, &profile);
+
+        assert_eq!(
+            params_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
     #[test]
     fn test_with_unit_struct() {
         let item_struct = parse_quote! { pub struct MyStruct; };
 
-        let params_struct = ParamsStruct::from(&item_struct);
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let params_struct = ParamsStruct::from(&ctx);
 
         assert_eq!(
             params_struct.to_token_stream().to_string(),
@@ -0,0 +1,347 @@
+use crate::components::field_span;
+use crate::options::is_builder_attribute;
+use crate::struct_builder::{BuilderContext, FieldContext, GenericsContext};
+use proc_macro2::TokenStream;
+use quote::{format_ident, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, parse_quote_spanned, Attribute, Field, Fields, Ident, ItemFn, ItemStruct, LitStr, Member, Meta, Path, Token};
+
+/// Serde field attributes that mean the same on a patch field, whose type is the field's type wrapped in an `Option`.
+/// Others, like `with` or `default = "path"`, depend on the field's type, so they're left out.
+const PATCH_SERDE_ATTRIBUTES: [&str; 6] = ["rename", "alias", "skip", "skip_serializing", "skip_deserializing", "flatten"];
+
+pub struct PatchStruct<'a> {
+    ctx: &'a BuilderContext,
+    fields: &'a Fields
+}
+
+impl<'a> From<&'a BuilderContext> for PatchStruct<'a> {
+    fn from(ctx: &'a BuilderContext) -> Self {
+        Self { ctx, fields: &ctx.fields }
+    }
+}
+
+impl ToTokens for PatchStruct<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let BuilderContext {
+            patch,
+            options,
+            generics,
+            attributes,
+            ..
+        } = &self.ctx;
+        let GenericsContext {
+            generics_decl,
+            where_clause,
+            ..
+        } = &generics;
+        let attrs = &attributes.outer_attrs;
+
+        if !options.patch {
+            return;
+        }
+
+        let include_generics = patch_includes_generics(self.ctx);
+
+        match &self.fields {
+            Fields::Named(_) => {
+                let punctuated_fields = self.punctuated_fields();
+                let item_struct: ItemStruct = if include_generics {
+                    parse_quote! {
+                        #(#attrs)*
+                        pub struct #patch #generics_decl #where_clause {
+                            #punctuated_fields
+                        }
+                    }
+                } else {
+                    parse_quote! {
+                        #(#attrs)*
+                        pub struct #patch {
+                            #punctuated_fields
+                        }
+                    }
+                };
+
+                item_struct.to_tokens(tokens);
+            },
+
+            Fields::Unnamed(_) => {
+                let punctuated_fields = self.punctuated_fields();
+                let item_struct: ItemStruct = if include_generics {
+                    parse_quote! {
+                        #(#attrs)*
+                        pub struct #patch #generics_decl ( #punctuated_fields ) #where_clause;
+                    }
+                } else {
+                    parse_quote! {
+                        #(#attrs)*
+                        pub struct #patch ( #punctuated_fields );
+                    }
+                };
+
+                item_struct.to_tokens(tokens);
+            },
+
+            Fields::Unit => return
+        }
+
+        if let Some(deserialize_set_fn) = self.deserialize_set_fn() {
+            let item_fn: ItemFn = parse_quote! {
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                fn #deserialize_set_fn<'de, D, T>(deserializer: D) -> ::core::result::Result<::core::option::Option<T>, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                    T: ::serde::Deserialize<'de>
+                {
+                    <T as ::serde::Deserialize<'de>>::deserialize(deserializer).map(::core::option::Option::Some)
+                }
+            };
+
+            item_fn.to_tokens(tokens);
+        }
+    }
+}
+
+impl<'a> PatchStruct<'a> {
+    fn punctuated_fields(&self) -> Punctuated<Field, Token![,]> {
+        let attrs = &self.ctx.attributes.outer_attrs;
+        let serialize = derives(attrs, "Serialize");
+        let deserialize_set_fn = self.deserialize_set_fn().map(|ident| LitStr::new(&ident.to_string(), ident.span()));
+
+        patch_fields(self.ctx)
+            .into_iter()
+            .map(|(_, _, field, field_ctx)| {
+                let span = field_span(field);
+                let optional = !field_ctx.required;
+                let mut field = field.clone();
+                let ty = &field.ty;
+                field.ty = parse_quote_spanned! {span=> ::core::option::Option<#ty> };
+                field.vis = parse_quote_spanned! {span=> pub };
+                field.attrs = field.attrs
+                    .into_iter()
+                    .filter(|attr| !is_builder_attribute(attr))
+                    .filter_map(patch_serde_attr)
+                    .collect();
+
+                // Unset fields are left out, so that `null` always means setting an `Option` field to `None`
+                let mut serde_metas: Vec<Meta> = vec![];
+                if serialize {
+                    serde_metas.push(parse_quote! { skip_serializing_if = "::core::option::Option::is_none" });
+                }
+                if let (true, Some(deserialize_set_fn)) = (optional, &deserialize_set_fn) {
+                    serde_metas.push(parse_quote! { default });
+                    serde_metas.push(parse_quote! { deserialize_with = #deserialize_set_fn });
+                }
+                if !serde_metas.is_empty() {
+                    field.attrs.push(parse_quote! { #[serde(#(#serde_metas),*)] });
+                }
+                field
+            })
+            .collect::<Punctuated<Field, Token![,]>>()
+    }
+
+    /// The function that deserializes a patch's `Option` fields, so that `null` is `Some(None)` rather than `None`, if the
+    /// patch derives `Deserialize` and has any.
+    fn deserialize_set_fn(&self) -> Option<Ident> {
+        let has_optional_fields = patch_fields(self.ctx).iter().any(|(_, _, _, field_ctx)| !field_ctx.required);
+        (has_optional_fields && derives(&self.ctx.attributes.outer_attrs, "Deserialize"))
+            .then(|| format_ident!("__{}_deserialize_set", self.ctx.patch))
+    }
+}
+
+/// Whether the field has `#[serde(flatten)]`, which the patch keeps for required fields only, since a flattened
+/// `Option` can't tell an unset field from one set to `None`.
+pub fn is_serde_flatten(field: &Field) -> bool {
+    field.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok())
+        .any(|metas| metas.iter().any(|meta| meta.path().is_ident("flatten")))
+}
+
+/// Whether the attributes derive a trait with the given name, e.g. `Deserialize` for `#[derive(serde::Deserialize)]`.
+pub fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated).ok())
+        .any(|paths| paths.iter().any(|path| path.segments.last().is_some_and(|segment| segment.ident == name)))
+}
+
+/// The attribute for a patch field, which is the field's own unless it's a `serde` attribute, which keeps only the
+/// [PATCH_SERDE_ATTRIBUTES].
+fn patch_serde_attr(attr: Attribute) -> Option<Attribute> {
+    if !attr.path().is_ident("serde") {
+        return Some(attr);
+    }
+
+    let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok()?;
+    let metas = metas
+        .into_iter()
+        .filter(|meta| PATCH_SERDE_ATTRIBUTES.iter().any(|name| meta.path().is_ident(name)))
+        .collect::<Punctuated<Meta, Token![,]>>();
+
+    (!metas.is_empty()).then(|| parse_quote! { #[serde(#metas)] })
+}
+
+/// The fields that can be patched, as their member in the patch, their member in the subject, the field itself and its
+/// analysis.
+pub fn patch_fields(ctx: &BuilderContext) -> Vec<(Member, Member, &Field, &FieldContext)> {
+    ctx.analysed_fields()
+        .enumerate()
+        .filter(|(_, (_, field_ctx))| field_ctx.options.has_setter())
+        .enumerate()
+        .map(|(patch_index, (subject_index, (field, field_ctx)))| match &field.ident {
+            Some(ident) => (Member::from(ident.clone()), Member::from(ident.clone()), field, field_ctx),
+            None => (Member::from(patch_index), Member::from(subject_index), field, field_ctx)
+        })
+        .collect()
+}
+
+/// Whether the patch struct needs the subject's generics, i.e. any of its fields are generic.
+pub fn patch_includes_generics(ctx: &BuilderContext) -> bool {
+    patch_fields(ctx)
+        .iter()
+        .any(|(_, _, _, field_ctx)| field_ctx.generic)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::PatchStruct;
+    use crate::struct_builder::BuilderContext;
+    use crate::test_util::{sample_named_item_struct, sample_unnamed_item_struct};
+    use proc_macro2::TokenStream;
+    use quote::ToTokens;
+    use syn::{parse_quote, File, ItemStruct};
+
+    #[test]
+    fn test_with_named_fields() {
+        let mut item_struct = sample_named_item_struct();
+        item_struct.attrs.push(parse_quote! { #[builder(patch)] });
+        let expected: File = parse_quote! {
+            #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+            pub struct MyStructPatch<T, I: Send, W>
+            where
+                W: Sync
+            {
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub public_field: ::core::option::Option<String>,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub private_field: ::core::option::Option<String>,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none", default, deserialize_with = "__MyStructPatch_deserialize_set")]
+                pub optional: ::core::option::Option<Option<usize> >,
+                #[serde(rename = "testMe")]
+                #[serde(skip_serializing_if = "::core::option::Option::is_none", default, deserialize_with = "__MyStructPatch_deserialize_set")]
+                pub test: ::core::option::Option<std::option::Option<String> >,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none", default, deserialize_with = "__MyStructPatch_deserialize_set")]
+                pub test2: ::core::option::Option<option::Option<T> >,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub dynamic: ::core::option::Option<Box<dyn Send> >,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub dynamic2: ::core::option::Option<Box<Option<dyn Send> > >,
+                #[serde(rename = "simpleGeneric")]
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub generic: ::core::option::Option<T>,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub generic_inline: ::core::option::Option<I>,
+                #[serde(skip_serializing_if = "::core::option::Option::is_none")]
+                pub generic_where: ::core::option::Option<W>
+            }
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            fn __MyStructPatch_deserialize_set<'de, D, T>(deserializer: D) -> ::core::result::Result<::core::option::Option<T>, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+                T: ::serde::Deserialize<'de>
+            {
+                <T as ::serde::Deserialize<'de> >::deserialize(deserializer).map(::core::option::Option::Some)
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let patch_struct = PatchStruct::from(&ctx);
+
+        assert_eq!(
+            patch_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_unnamed_fields() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(patch)]
+            pub struct MyStruct<T>(
+                #[builder(skip)]
+                u64,
+                pub String,
+                Option<u8>,
+                #[builder(setter(skip))]
+                T
+            );
+        };
+        let expected: ItemStruct = parse_quote! {
+            pub struct MyStructPatch(
+                pub ::core::option::Option<String>,
+                pub ::core::option::Option<Option<u8> >
+            );
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let patch_struct = PatchStruct::from(&ctx);
+
+        assert_eq!(
+            patch_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_with_type_dependent_serde_attributes() {
+        let item_struct: ItemStruct = parse_quote! {
+            #[builder(patch)]
+            #[derive(serde::Deserialize)]
+            pub struct MyStruct {
+                #[serde(rename = "createdAt", with = "time::serde::rfc3339")]
+                pub created_at: OffsetDateTime,
+                #[serde(default = "default_port")]
+                pub port: u16,
+                #[serde(flatten, default)]
+                pub location: Location
+            }
+        };
+        let expected: ItemStruct = parse_quote! {
+            #[derive(serde::Deserialize)]
+            pub struct MyStructPatch {
+                #[serde(rename = "createdAt")]
+                pub created_at: ::core::option::Option<OffsetDateTime>,
+                pub port: ::core::option::Option<u16>,
+                #[serde(flatten)]
+                pub location: ::core::option::Option<Location>
+            }
+        };
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let patch_struct = PatchStruct::from(&ctx);
+
+        assert_eq!(
+            patch_struct.to_token_stream().to_string(),
+            expected.to_token_stream().to_string()
+        );
+    }
+
+    #[test]
+    fn test_without_patch_option() {
+        let item_struct = sample_unnamed_item_struct();
+
+        let ctx = BuilderContext::new(&item_struct).unwrap();
+        let patch_struct = PatchStruct::from(&ctx);
+
+        assert_eq!(
+            patch_struct.to_token_stream().to_string(),
+            TokenStream::new().to_string()
+        );
+    }
+}
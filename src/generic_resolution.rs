@@ -1,9 +1,11 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::punctuated::Punctuated;
-use syn::{Field, GenericParam, Generics, ReturnType, Token, Type};
+use syn::{Expr, Field, GenericArgument, GenericParam, Generics, Path, PathArguments, ReturnType, Token, Type};
 
 struct SearchIdents {
     types: Vec<Ident>,
+    consts: Vec<Ident>,
     lifetimes: Vec<Ident>
 }
 
@@ -15,6 +17,7 @@ pub fn field_has_generic(generics: &Generics, field: &Field) -> bool {
 fn generic_params_contain_type(generic_params: &Punctuated<GenericParam, Token![,]>, ty: &Type) -> bool {
     let mut search_idents = SearchIdents {
         types: vec![],
+        consts: vec![],
         lifetimes: vec![],
     };
 
@@ -22,45 +25,45 @@ fn generic_params_contain_type(generic_params: &Punctuated<GenericParam, Token![
         match param {
             GenericParam::Lifetime(lp) => search_idents.lifetimes.push(lp.lifetime.ident.clone()),
             GenericParam::Type(tp) => search_idents.types.push(tp.ident.clone()),
-            GenericParam::Const(cp) => search_idents.types.push(cp.ident.clone())
+            GenericParam::Const(cp) => search_idents.consts.push(cp.ident.clone())
         }
     }
 
-    search_idents_contain_type(&search_idents, &ty)
+    search_idents_contain_type(&search_idents, ty)
 }
 
 fn search_idents_contain_type(search_idents: &SearchIdents, ty: &Type) -> bool {
     match ty {
-        Type::Array(array) => search_idents_contain_type(&search_idents, &array.elem),
+        Type::Array(array) =>
+            search_idents_contain_type(search_idents, &array.elem) || search_idents_contain_expr(search_idents, &array.len),
 
         Type::BareFn(bare_fn) => {
             let mut contains = false;
             if let Some(lts) = &bare_fn.lifetimes {
-                contains = contains || generic_params_contain_type(&lts.lifetimes, &ty)
+                contains = contains || generic_params_contain_type(&lts.lifetimes, ty)
             }
             for input in &bare_fn.inputs {
-                contains = contains || search_idents_contain_type(&search_idents, &input.ty)
+                contains = contains || search_idents_contain_type(search_idents, &input.ty)
             }
             if let ReturnType::Type(_, inner_ty) = &bare_fn.output {
-                contains = contains || search_idents_contain_type(&search_idents, &inner_ty);
+                contains = contains || search_idents_contain_type(search_idents, inner_ty);
             }
             contains
         }
 
-        Type::Group(group) => search_idents_contain_type(&search_idents, &group.elem),
+        Type::Group(group) => search_idents_contain_type(search_idents, &group.elem),
 
-        Type::Paren(paren) => search_idents_contain_type(&search_idents, &paren.elem),
+        Type::Paren(paren) => search_idents_contain_type(search_idents, &paren.elem),
 
         Type::Path(path) => {
             if let Some(qself) = &path.qself {
-                search_idents_contain_type(&search_idents, &qself.ty)
+                search_idents_contain_type(search_idents, &qself.ty) || search_idents_contain_path_args(search_idents, &path.path)
             } else {
-                let p = &path.path;
-                search_idents.types.iter().any(|ty| p.is_ident(ty))
+                search_idents_contain_path(search_idents, &path.path)
             }
         },
 
-        Type::Ptr(ptr) => search_idents_contain_type(&search_idents, &ptr.elem),
+        Type::Ptr(ptr) => search_idents_contain_type(search_idents, &ptr.elem),
 
         Type::Reference(reference) => {
             let mut contains = false;
@@ -69,12 +72,12 @@ fn search_idents_contain_type(search_idents: &SearchIdents, ty: &Type) -> bool {
                 contains = contains || search_idents.lifetimes.contains(&lt.ident)
             }
             
-            contains || search_idents_contain_type(&search_idents, &reference.elem)
+            contains || search_idents_contain_type(search_idents, &reference.elem)
         },
 
-        Type::Slice(slice) => search_idents_contain_type(&search_idents, &slice.elem),
+        Type::Slice(slice) => search_idents_contain_type(search_idents, &slice.elem),
         
-        Type::Tuple(tuple) => tuple.elems.iter().any(|el| search_idents_contain_type(&search_idents, &el)),
+        Type::Tuple(tuple) => tuple.elems.iter().any(|el| search_idents_contain_type(search_idents, el)),
 
         // todo - unknown
         Type::ImplTrait(_) => false,
@@ -86,6 +89,58 @@ fn search_idents_contain_type(search_idents: &SearchIdents, ty: &Type) -> bool {
     }
 }
 
+fn search_idents_contain_path(search_idents: &SearchIdents, path: &Path) -> bool {
+    // A const param passed as a generic argument, like `N` in `ArrayVec<T, N>`, parses as a type
+    if let Some(ident) = path.get_ident() {
+        if search_idents.consts.contains(ident) {
+            return true;
+        }
+    }
+
+    // A type param may be followed by an associated item, like `T::Output`
+    let first_is_type = path.leading_colon.is_none() && path.segments
+        .first()
+        .is_some_and(|segment| search_idents.types.contains(&segment.ident));
+
+    first_is_type || search_idents_contain_path_args(search_idents, path)
+}
+
+fn search_idents_contain_path_args(search_idents: &SearchIdents, path: &Path) -> bool {
+    path.segments.iter().any(|segment| match &segment.arguments {
+        PathArguments::None => false,
+
+        PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+            GenericArgument::Lifetime(lt) => search_idents.lifetimes.contains(&lt.ident),
+            GenericArgument::Type(ty) => search_idents_contain_type(search_idents, ty),
+            GenericArgument::Const(expr) => search_idents_contain_expr(search_idents, expr),
+            GenericArgument::AssocType(assoc) => search_idents_contain_type(search_idents, &assoc.ty),
+            GenericArgument::AssocConst(assoc) => search_idents_contain_expr(search_idents, &assoc.value),
+            _ => false
+        }),
+
+        PathArguments::Parenthesized(args) => {
+            let output_contains = match &args.output {
+                ReturnType::Type(_, ty) => search_idents_contain_type(search_idents, ty),
+                ReturnType::Default => false
+            };
+            output_contains || args.inputs.iter().any(|input| search_idents_contain_type(search_idents, input))
+        }
+    })
+}
+
+/// Const expressions, like array lengths, can only use const params.
+fn search_idents_contain_expr(search_idents: &SearchIdents, expr: &Expr) -> bool {
+    tokens_contain_ident(&search_idents.consts, expr.to_token_stream())
+}
+
+fn tokens_contain_ident(idents: &[Ident], tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => tokens_contain_ident(idents, group.stream()),
+        _ => false
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::generic_resolution::field_has_generic;
@@ -109,6 +164,9 @@ mod tests {
         test_array_no_generics |{      }|{ values: [A; 3]  }| false,
         test_array_has_type    |{ <T>  }|{ values: [T; 2]       }| true,
         test_array_has_liftime |{ <'a> }|{ values: [&'a B; 1] }| true,
+        test_array_has_const_len      |{ <const N: usize> }|{ values: [u8; N]            }| true,
+        test_array_has_const_expr_len |{ <const N: usize> }|{ values: [u8; { N * 2 }]    }| true,
+        test_array_other_const_len    |{ <const N: usize> }|{ values: [u8; M]            }| false,
         
         test_bare_fn_no_generics        |{      }|{ block: fn(B) -> u64         }| false,
         test_bare_fn_has_input_type     |{ <T>  }|{ block: fn(T) -> u64         }| true,
@@ -124,6 +182,12 @@ mod tests {
         test_path_qself_has_type     |{ <T>  }|{ value: <T>::Type                }| true,
         test_path_qself_has_lifetime |{ <'a> }|{ value: <&'a Self as Send>::Type }| true,
         test_path_has_type           |{ <T>  }|{ value: T                        }| true,
+        test_path_has_assoc_type     |{ <T>  }|{ value: T::Output                }| true,
+        test_path_arg_has_type       |{ <T>  }|{ value: ::std::option::Option<T> }| true,
+        test_path_arg_has_lifetime   |{ <'a> }|{ value: Cow<'a, str>             }| true,
+        test_path_arg_has_const      |{ <const N: usize> }|{ value: ArrayVec<u8, N>     }| true,
+        test_path_arg_has_const_expr |{ <const N: usize> }|{ value: ArrayVec<u8, { N }> }| true,
+        test_path_other_ident        |{ <const N: usize> }|{ value: ArrayVec<u8, 4>     }| false,
         
         test_ptr_no_generics  |{      }|{ value: *const A }| false,
         test_ptr_has_type     |{ <T>  }|{ value: *mut T   }| true,
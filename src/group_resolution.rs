@@ -0,0 +1,66 @@
+use syn::visit_mut::VisitMut;
+use syn::{ItemStruct, Type, TypeGroup};
+
+struct RemoveGroups;
+
+impl VisitMut for RemoveGroups {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        // Paths bind tighter than anything around them, so their invisible group is safe to drop
+        if let Type::Group(TypeGroup { elem, .. }) = ty {
+            if matches!(elem.as_ref(), Type::Path(_) | Type::Group(_)) {
+                *ty = *elem.clone();
+                return self.visit_type_mut(ty);
+            }
+        }
+        syn::visit_mut::visit_type_mut(self, ty)
+    }
+}
+
+/// Remove the invisible groups `macro_rules!` puts around `$ty:ty` fragments from the field types, so they're recognized
+/// like the types they contain, e.g. `Option<u8>` as optional.
+pub fn resolve_groups(item: &ItemStruct) -> ItemStruct {
+    let mut item = item.clone();
+    for field in item.fields.iter_mut() {
+        RemoveGroups.visit_type_mut(&mut field.ty);
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::group_resolution::resolve_groups;
+    use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+    use quote::{quote, ToTokens};
+    use syn::{parse_quote, ItemStruct, Type};
+
+    fn grouped(tokens: TokenStream) -> TokenTree {
+        TokenTree::Group(Group::new(Delimiter::None, tokens))
+    }
+
+    #[test]
+    fn test_resolve_groups() {
+        let option = grouped(quote! { Option<u8> });
+        let inner = grouped(quote! { String });
+        let object = grouped(quote! { dyn Send + Sync });
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub age: #option,
+                pub names: Vec<#inner>,
+                pub object: &'static #object
+            }
+        };
+        let expected: Vec<Type> = vec![
+            parse_quote! { Option<u8> },
+            parse_quote! { Vec<String> },
+            parse_quote! { &'static #object }
+        ];
+
+        let item = resolve_groups(&item);
+
+        assert!(matches!(&item.fields.iter().next().unwrap().ty, Type::Path(_)));
+        assert_eq!(
+            item.fields.iter().map(|field| field.ty.to_token_stream().to_string()).collect::<Vec<_>>(),
+            expected.iter().map(|ty| ty.to_token_stream().to_string()).collect::<Vec<_>>()
+        );
+    }
+}
@@ -112,24 +112,159 @@ extern crate proc_macro;
 mod components;
 mod struct_builder;
 mod generic_resolution;
+mod self_resolution;
+mod cfg_resolution;
+mod group_resolution;
+mod options;
 #[cfg(test)]
 mod test_util;
 
 use crate::struct_builder::StructBuilder;
 use quote::quote;
-use syn::{parse_macro_input, ItemStruct};
+use syn::{parse_macro_input, parse_quote, ItemStruct};
 
 /// Derive the builder pattern for a struct.
 ///
 /// A struct builder enforces required fields to be specified and allows optional arguments to be specified post-construction.
 /// This is done by defining a "params" struct that the builder depends on to be initialized. This struct defines all the fields
 /// in the original struct that don't have the "Option" type. Once the builder is initialized with the params, both required and optional fields
-/// can be updated by calling builder methods (using the identifiers `with_<field>`).
+/// can be updated by calling builder methods (using the identifiers `with_<field>`). `Self` in field types and the where
+/// clause means the struct everywhere it's used, so recursive structs like `children: Vec<Self>` work as expected.
+/// Defaults of generic params, like `struct Buffer<T = String>`, are kept on the params struct and the builder, so
+/// `BufferParams` and `BufferBuilder` can be named without arguments too.
+///
+/// The struct's attributes are carried over to the params struct, except `#[non_exhaustive]`, so other crates can still
+/// create it. Instead, params structs of a `#[non_exhaustive]` struct get `<Struct>Params::new(...)`, which takes the
+/// required fields in order.
+///
+/// Fields with `#[cfg]` attributes keep them on everything generated for them, i.e. their params fields, setters,
+/// patch fields and metadata, so a field that's configured out is left out everywhere. Tuple fields after it keep
+/// their `with_<index>` names, but are read and written at the index they end up with.
+///
+/// Code generated for a field is located at that field, so errors in it and go-to-definition on e.g. `with_<field>`
+/// lead to the field rather than to `#[builder]`. Generated type names take the struct name's span, so structs declared
+/// inside `macro_rules!` macros get `<Struct>Params` and `<Struct>Builder` types that the macro's caller can name.
+///
+/// Alongside `with_<field>`, the builder has functional update methods for each field: `map_<field>` replaces the value
+/// with the result of a closure over the current value, `with_<field>_if` sets the value only if a condition holds, and
+/// `maybe_with_<field>` sets the value only if one is given. `modify` gives mutable access to the whole subject.
+/// Fields whose builder methods would share a name, like `with_foo_if` from both `foo` and `foo_if`, fail to compile with
+/// an error naming both, so one of them needs renaming or `setter(skip)`. Only fields under contradicting `#[cfg]`s, like
+/// `cfg(unix)` and `cfg(not(unix))`, may share names. Presets can't be named after the builder's own methods (`build`,
+/// `modify`, `apply`, `apply_env`, `set_by_name`, `apply_pairs`, `from_env` and `from_params`), any other builder
+/// method or each other, and profiles can't share names either.
+///
+/// Field doc comments are kept on the params struct's fields and copied to the `with_<field>` setters, and a
+/// `#[deprecated]` field makes its builder methods deprecated too. The params struct, builder and `builder` function are
+/// documented with the struct's required fields.
+///
+/// The struct, params struct and builder share constants describing the struct's fields: `FIELDS` lists a
+/// `<Struct>FieldInfo` for each field with its name (or index), type, doc comment, and whether it's required, generic
+/// or skipped, and `REQUIRED_FIELD_COUNT`, `OPTIONAL_FIELD_COUNT`, `GENERIC_REQUIRED_FIELD_COUNT`,
+/// `GENERIC_OPTIONAL_FIELD_COUNT` and `SKIPPED_FIELD_COUNT` count them (the first two counting non-generic fields only).
+///
+/// # `no_std`
+///
+/// Generated code names everything by its full `::core` path, so it works in `#![no_std]` crates, and in crates that
+/// shadow prelude names like `Option` or `From`. Only `set_by_name` and `try_convert` allocate (for their error
+/// messages): they take `String` and `Vec` from `alloc`, through a hidden `extern crate alloc` next to the struct, which
+/// is the same with or without `std`, so `default-features = false, features = ["alloc"]` is enough for them. `env` and
+/// `clap` need the `std` feature, and options that need a disabled feature fail to compile with an error saying so.
+/// The errors of `set_by_name` and `try_convert` implement [std::error::Error] only with the `std` feature, through a
+/// hidden `extern crate std`, so they do so in `#![no_std]` crates that enable it too.
+///
+/// # Options
+///
+/// - `#[builder(const)]` makes `builder`, the `with_<field>` setters, and `build` `const fn`s, and adds a
+///   `const fn from_params` to the struct as a `const` alternative to `From<Params>`. Setters replace the field's value,
+///   so field types can't need dropping (e.g. `&'static str` rather than `String`). A field whose type needs dropping has
+///   to be marked with `#[builder(setter(const = false))]`, which makes its setter and `build` non-`const`, as well as
+///   `builder` (with no `from_params`) if the field is required.
+/// - `#[builder(patch)]` defines a `<Struct>Patch` struct with every field that has a setter wrapped in an [Option],
+///   so `Option` fields distinguish "unset" (`None`) from "set to `None`" (`Some(None)`). The patch inherits the struct's
+///   attributes like the params struct, and comes with `Patch::empty`, `patch.merge(other)`, `subject.apply(patch)`,
+///   `builder.apply(patch)` and `Struct::diff(&from, &to)` (for fields that are `PartialEq + Clone`).
+///   With a derived serde `Deserialize`, `null` for an `Option` field is `Some(None)`, so it clears the field, and with
+///   `Serialize`, unset fields are left out. Of the serde field attributes, `rename`, `alias`, `skip`, `skip_serializing`,
+///   `skip_deserializing` and `flatten` are carried over to the patch. The others depend on the field's type, like
+///   `with`, and are left out, so the patch's fields (de)serialize with their own types. A flattened field is set in the
+///   patch only if all of its fields are there, and `flatten` on an `Option` field is an error, as the patch couldn't tell
+///   it being unset from it being `None`.
+/// - `#[builder(env(prefix = "APP_"))]` adds `Struct::from_env()` and `builder.apply_env()`, which parse fields with
+///   [FromStr](std::str::FromStr) from environment variables named by the prefix and the uppercase field name (or index).
+///   `from_env` fails with a `<Struct>EnvError` naming the variable if a required field's variable is missing, or any
+///   variable can't be parsed. Optional fields are left as `None`, and `apply_env` only sets fields whose variables exist.
+///   Optional fields whose types aren't [FromStr](std::str::FromStr) can be left out with `#[builder(env = false)]`.
+/// - `#[builder(clap)]` defines a `<Struct>Args` struct deriving `clap::Args` (so the crate needs `clap` with its `derive`
+///   feature), where required fields are required flags, optional fields are optional flags, and field doc comments are the
+///   help text. `From<<Struct>Args>` is implemented for the struct through the params struct and builder. This needs named fields.
+/// - `#[builder(set_by_name)]` adds `builder.set_by_name("field", "value")`, which parses the value with
+///   [FromStr](std::str::FromStr) into the field with that name (or index), and `builder.apply_pairs(pairs)` for many at once.
+///   Unknown fields and unparsable values fail with a `<Struct>SetFieldError`, suggesting the closest field name if any. Every
+///   field with a setter has to be [FromStr](std::str::FromStr), unless it's left out with `#[builder(set_by_name = false)]`.
+/// - `#[builder(profile(name = "admin", required(email, role)))]` adds another way to create the builder, with its own
+///   required fields: `Struct::admin_builder(params)` takes a `<Struct>AdminParams` with the listed fields, where `Option`
+///   fields are given without the `Option`. Every required field of the struct has to be listed. This needs named fields,
+///   and can be repeated for more profiles.
+/// - `#[builder(params_field_vis = "pub(crate)")]` sets the visibility of the params structs' fields, which are `pub` by
+///   default so the params can be given wherever the builder is usable, even for private fields of the struct.
+/// - `#[builder(preset(name = "local_dev", port = 8080, host = Some("localhost".into())))]` adds
+///   `<Struct>Builder::local_dev(params)`, a builder from the params struct with the listed optional or skipped fields set
+///   to the given values. Values of `Option` fields are wrapped in `Some` unless they're written as `Some(..)` or `None`.
+///   Required fields come from the params struct, so they can't be in a preset. This needs named fields, and can be
+///   repeated for more presets, which are listed in the builder's docs.
+///
+/// # Field options
+///
+/// - `#[builder(skip)]` / `#[builder(skip = expr)]` leaves the field out of the params struct and the builder methods,
+///   initializing it with [Default::default] or the given expression. Fields of type `PhantomData` are skipped automatically.
+/// - `#[builder(setter(skip))]` keeps the field in the params struct but generates no builder methods for it.
+/// - `#[builder(setter(vis = "pub(crate)"))]` sets the visibility of the field's builder methods, which are `pub` by default.
+/// - `#[builder(setter(const = false))]` keeps the field's setter from being a `const fn` in a `const` builder, for field
+///   types that need dropping.
+/// - `#[builder(boxed)]` on a field of type `Box<dyn Trait>`, `Arc<dyn Trait>` or `Rc<dyn Trait>`, or an `Option` of one,
+///   makes `with_<field>` take `value: impl Trait + 'static` and do the wrapping (keeping the trait object's own
+///   lifetime if it has one), and for other pointed-to types it takes the pointed-to type, e.g. `with_<field>(value:
+///   Config)` for an `Arc<Config>` field. Without it, the setter takes the field's type, and trait object fields get
+///   `with_<field>_boxed(value: impl Trait + 'static)` alongside it, which does the wrapping. This can't be combined with
+///   `const`, and needs the pointer type written out rather than a type alias, which the macro can't see through.
+/// - `#[builder(params_field_vis = "pub(super)")]` sets the visibility of the field in the params structs (or of a
+///   flattened field's own fields), instead of the struct's `params_field_vis`.
+/// - `#[builder(nested)]` on a field whose type (or `Option` type) is another builder struct adds
+///   `with_<field>_builder(params, |builder| ...)`, which builds the field's value with its own builder, and
+///   `update_<field>(|builder| ...)`, which opens a builder over the current value (if any).
+/// - `#[builder(flatten(created_by: String, note: Option<String>))]` on a field whose type is another builder struct puts
+///   that struct's required fields in the params struct in place of the field, and adds setters for all the listed fields
+///   to the builder. A macro only sees the struct it's applied to, so the nested struct's fields with setters have to be
+///   repeated, and a constant assertion fails to compile when they aren't all listed as required or optional as they
+///   are. Fields with `setter(skip)` aren't listed, and a struct with a required one can't be flattened. This needs named
+///   fields, and can't be combined with `const`, `env` or `clap`.
+/// - `#[builder(params_type = &'static str)]` on a required field declares it with that type in the params struct, and
+///   `builder` converts it with [Into]. `#[builder(params_type = String, convert = PathBuf::from)]` converts it with the
+///   given function instead, and `#[builder(params_type = u32, try_convert)]` with [TryFrom] (or `try_convert = path`, a
+///   function returning a `Result` whose error is [Display](std::fmt::Display)). A fallible conversion makes `builder`
+///   and the presets return a `Result` with a `<Struct>ParamsError` naming the field, and replaces `From<Params>` with
+///   `TryFrom<Params>`. The type has to be one a struct field can have, so `impl Trait` types need a generic parameter
+///   instead. This can't be combined with `const`, `env` or `clap`.
+/// - `#[builder(name = "depth")]` names the field for `set_by_name` and `env` variables instead of its identifier or index.
+///   Two fields can't end up with the same name.
+/// - `#[builder(set_by_name = false)]` leaves the field out of `set_by_name`, so its type doesn't need to be
+///   [FromStr](std::str::FromStr). It keeps its setters.
+/// - `#[builder(env = false)]` on an optional field leaves it out of `from_env` and `apply_env`, so its type doesn't need
+///   to be [FromStr](std::str::FromStr).
 ///
 #[proc_macro_attribute]
-pub fn builder(_attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let original_item = parse_macro_input!(item as ItemStruct);
+pub fn builder(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut original_item = parse_macro_input!(item as ItemStruct);
+
+    // Options given to the macro are handled the same as `#[builder(...)]` on the struct
+    let attr = proc_macro2::TokenStream::from(attr);
+    if !attr.is_empty() {
+        original_item.attrs.push(parse_quote! { #[builder(#attr)] });
+    }
+
     let struct_builder = StructBuilder(original_item.clone());
+    let original_item = options::strip_builder_attributes(original_item);
 
     proc_macro::TokenStream::from(quote! { 
         #original_item
@@ -145,7 +280,7 @@ pub fn builder(_attr: proc_macro::TokenStream, item: proc_macro::TokenStream) ->
         This macro type does not support inheriting existing attributes, such as other derived traits.
     "#
 )]
-#[proc_macro_derive(StructBuilder)]
+#[proc_macro_derive(StructBuilder, attributes(builder))]
 pub fn derive_builder(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let item = parse_macro_input!(item as ItemStruct);
     let struct_builder = StructBuilder(item);
@@ -0,0 +1,1154 @@
+use crate::cfg_resolution::cfg_disjoint;
+use crate::components::{is_required, is_serde_flatten, is_trait_object_pointer, nested_builder_type, option_inner_type, smart_pointer};
+use crate::struct_builder::FieldContext;
+use quote::{format_ident, ToTokens};
+use syn::ext::IdentExt;
+use std::collections::HashMap;
+use syn::{parenthesized, parse_quote, token, Attribute, Expr, Field, Fields, Ident, ItemStruct, LitBool, LitStr, Path, Token, Type, Visibility};
+
+const BUILDER_ATTRIBUTE_NAME: &str = "builder";
+
+#[derive(Default)]
+pub struct StructOptions {
+    pub const_fn: bool,
+    pub patch: bool,
+    pub env: Option<EnvOptions>,
+    pub clap: bool,
+    pub set_by_name: bool,
+    pub profiles: Vec<ProfileOptions>,
+    pub presets: Vec<PresetOptions>,
+    pub params_field_vis: Option<Visibility>
+}
+
+#[derive(Default)]
+pub struct EnvOptions {
+    pub prefix: String
+}
+
+#[derive(Clone)]
+pub struct ProfileOptions {
+    pub name: Ident,
+    pub required: Vec<Ident>
+}
+
+pub struct PresetOptions {
+    pub name: Ident,
+    pub values: Vec<(Ident, Expr)>
+}
+
+#[derive(Default)]
+pub struct FieldOptions {
+    pub skip: Option<Skip>,
+    pub setter: SetterOptions,
+    pub name: Option<String>,
+    pub nested: bool,
+    pub flatten: Option<Vec<Field>>,
+    pub params_type: Option<Type>,
+    pub convert: Option<Convert>,
+    pub boxed: bool,
+    pub params_field_vis: Option<Visibility>,
+    pub set_by_name: Option<bool>,
+    pub env: Option<bool>
+}
+
+#[derive(Default)]
+pub struct SetterOptions {
+    pub skip: bool,
+    pub vis: Option<Visibility>,
+    pub const_fn: Option<bool>
+}
+
+pub enum Skip {
+    Default,
+    Value(Expr)
+}
+
+/// How a params struct field given as a `params_type` becomes the field's value.
+pub enum Convert {
+    Infallible(Path),
+    Fallible(Path)
+}
+
+impl StructOptions {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs.iter().filter(|attr| is_builder_attribute(attr)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("const") {
+                    options.const_fn = true;
+                    Ok(())
+                } else if meta.path.is_ident("patch") {
+                    options.patch = true;
+                    Ok(())
+                } else if meta.path.is_ident("env") {
+                    let mut env = EnvOptions::default();
+                    if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                        meta.parse_nested_meta(|meta| {
+                            if meta.path.is_ident("prefix") {
+                                env.prefix = meta.value()?.parse::<LitStr>()?.value();
+                                Ok(())
+                            } else {
+                                Err(meta.error("unsupported builder env option"))
+                            }
+                        })?;
+                    }
+                    options.env = Some(env);
+                    Ok(())
+                } else if meta.path.is_ident("clap") {
+                    options.clap = true;
+                    Ok(())
+                } else if meta.path.is_ident("params_field_vis") {
+                    let vis: LitStr = meta.value()?.parse()?;
+                    options.params_field_vis = Some(vis.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("set_by_name") {
+                    options.set_by_name = true;
+                    Ok(())
+                } else if meta.path.is_ident("profile") {
+                    let mut name = None;
+                    let mut required = vec![];
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("name") {
+                            name = Some(meta.value()?.parse::<LitStr>()?.parse::<Ident>()?);
+                            Ok(())
+                        } else if meta.path.is_ident("required") {
+                            let content;
+                            parenthesized!(content in meta.input);
+                            required = content.parse_terminated(Ident::parse_any, Token![,])?.into_iter().collect();
+                            Ok(())
+                        } else {
+                            Err(meta.error("unsupported builder profile option"))
+                        }
+                    })?;
+                    let Some(name) = name else {
+                        return Err(meta.error("profiles need a name, `profile(name = \"..\", required(..))`"));
+                    };
+                    options.profiles.push(ProfileOptions { name, required });
+                    Ok(())
+                } else if meta.path.is_ident("preset") {
+                    let mut name = None;
+                    let mut values = vec![];
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("name") {
+                            name = Some(meta.value()?.parse::<LitStr>()?.parse::<Ident>()?);
+                        } else if let Some(field) = meta.path.get_ident() {
+                            values.push((field.clone(), meta.value()?.parse::<Expr>()?));
+                        } else {
+                            return Err(meta.error("preset values need a field name, `field = expr`"));
+                        }
+                        Ok(())
+                    })?;
+                    let Some(name) = name else {
+                        return Err(meta.error("presets need a name, `preset(name = \"..\", field = expr, ..)`"));
+                    };
+                    options.presets.push(PresetOptions { name, values });
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported builder option"))
+                }
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+impl StructOptions {
+    /// The visibility of the params struct's fields, `pub` unless specified otherwise.
+    pub fn params_field_vis(&self) -> Visibility {
+        self.params_field_vis.clone().unwrap_or(parse_quote! { pub })
+    }
+}
+
+impl ProfileOptions {
+    /// The name of the subject's function creating a builder for the profile, i.e. `admin_builder`.
+    pub fn builder_fn(&self) -> Ident {
+        format_ident!("{}_builder", self.name.unraw())
+    }
+
+    /// The profile's params struct, i.e. `UserAdminParams` for profile `admin` of `User`.
+    pub fn params(&self, subject: &Ident) -> Ident {
+        let name = self.name
+            .unraw()
+            .to_string()
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+            })
+            .collect::<String>();
+        format_ident!("{}{}Params", subject, name)
+    }
+
+    /// Whether the field is one of the profile's required fields.
+    pub fn requires(&self, field: &Field) -> bool {
+        field.ident.as_ref().is_some_and(|ident| self.required.contains(ident))
+    }
+}
+
+impl FieldOptions {
+    pub fn parse(field: &Field) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in field.attrs.iter().filter(|attr| is_builder_attribute(attr)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    options.skip = if meta.input.peek(Token![=]) {
+                        Some(Skip::Value(meta.value()?.parse()?))
+                    } else {
+                        Some(Skip::Default)
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("set_by_name") {
+                    options.set_by_name = Some(meta.value()?.parse::<LitBool>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("env") {
+                    options.env = Some(meta.value()?.parse::<LitBool>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("nested") {
+                    options.nested = true;
+                    Ok(())
+                } else if meta.path.is_ident("flatten") {
+                    // A macro only sees the struct it's applied to, so the nested struct's fields are repeated here
+                    if !meta.input.peek(token::Paren) {
+                        return Err(meta.error("`flatten` needs the nested struct's fields, `flatten(field: Type, ..)`"));
+                    }
+                    let content;
+                    parenthesized!(content in meta.input);
+                    let fields = content.parse_terminated(Field::parse_named, Token![,])?;
+                    options.flatten = Some(fields.into_iter().collect());
+                    Ok(())
+                } else if meta.path.is_ident("params_field_vis") {
+                    let vis: LitStr = meta.value()?.parse()?;
+                    options.params_field_vis = Some(vis.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("boxed") {
+                    options.boxed = true;
+                    Ok(())
+                } else if meta.path.is_ident("params_type") {
+                    options.params_type = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("convert") {
+                    options.convert = Some(Convert::Infallible(meta.value()?.parse()?));
+                    Ok(())
+                } else if meta.path.is_ident("try_convert") {
+                    options.convert = if meta.input.peek(Token![=]) {
+                        Some(Convert::Fallible(meta.value()?.parse()?))
+                    } else {
+                        Some(Convert::Fallible(parse_quote! { ::core::convert::TryFrom::try_from }))
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("setter") {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("skip") {
+                            options.setter.skip = true;
+                            Ok(())
+                        } else if meta.path.is_ident("vis") {
+                            let vis: LitStr = meta.value()?.parse()?;
+                            options.setter.vis = Some(vis.parse()?);
+                            Ok(())
+                        } else if meta.path.is_ident("const") {
+                            options.setter.const_fn = Some(meta.value()?.parse::<LitBool>()?.value());
+                            Ok(())
+                        } else {
+                            Err(meta.error("unsupported builder setter option"))
+                        }
+                    })
+                } else {
+                    Err(meta.error("unsupported builder field option"))
+                }
+            })?;
+        }
+
+        if options.skip.is_none() && is_phantom_data(&field.ty) {
+            options.skip = Some(Skip::Value(parse_quote! { ::core::marker::PhantomData }));
+        }
+
+        Ok(options)
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.skip.is_some()
+    }
+
+    /// Whether the builder has setters for the field.
+    pub fn has_setter(&self) -> bool {
+        !self.is_skipped() && !self.setter.skip
+    }
+
+    /// Whether the field can be set with `set_by_name`, i.e. it has setters and isn't `set_by_name = false`.
+    pub fn is_set_by_name(&self) -> bool {
+        self.has_setter() && self.set_by_name != Some(false)
+    }
+
+    /// Whether the field is read from its environment variable, i.e. it isn't skipped and isn't `env = false`.
+    pub fn is_read_from_env(&self) -> bool {
+        !self.is_skipped() && self.env != Some(false)
+    }
+
+    /// The field's name as given with `name = "..."`, otherwise its identifier or, for tuple structs, its index.
+    pub fn field_name(&self, index: usize, field: &Field) -> String {
+        match (&self.name, &field.ident) {
+            (Some(name), _) => name.clone(),
+            (None, Some(ident)) => ident.unraw().to_string(),
+            (None, None) => index.to_string()
+        }
+    }
+
+    /// Whether the field's params struct value goes through a conversion that can fail.
+    pub fn is_fallible(&self) -> bool {
+        matches!(self.convert, Some(Convert::Fallible(_)))
+    }
+
+    /// The field as it's declared in the params struct, with its `params_type` if it has one.
+    pub fn params_field(&self, field: &Field) -> Field {
+        let mut field = field.clone();
+        if let Some(params_type) = &self.params_type {
+            field.ty = params_type.clone();
+        }
+        field
+    }
+
+    /// The visibility of the field in the params struct, the struct's `params_field_vis` unless specified otherwise.
+    pub fn params_field_vis(&self, default: &Visibility) -> Visibility {
+        self.params_field_vis.clone().unwrap_or_else(|| default.clone())
+    }
+
+    /// Whether the field's setter is a `const fn`, which it is in a `const` builder unless `setter(const = false)`.
+    pub fn has_const_setter(&self, const_fn: bool) -> bool {
+        const_fn && self.setter.const_fn != Some(false)
+    }
+
+    /// The visibility of the builder's setters for the field, `pub` unless specified otherwise.
+    pub fn setter_vis(&self) -> Visibility {
+        self.setter.vis.clone().unwrap_or(parse_quote! { pub })
+    }
+}
+
+impl Skip {
+    /// The expression a skipped field is initialized with.
+    pub fn value(&self) -> Expr {
+        match self {
+            Skip::Default => parse_quote! { ::core::default::Default::default() },
+            Skip::Value(expr) => expr.clone()
+        }
+    }
+}
+
+/// Check the struct's own `#[builder(...)]` options, before its fields are analysed.
+pub fn validate_struct(item: &ItemStruct, struct_options: &StructOptions) -> syn::Result<()> {
+    if struct_options.clap && !matches!(item.fields, Fields::Named(_)) {
+        return Err(syn::Error::new_spanned(&item.ident, "`clap` arguments need a struct with named fields"));
+    }
+
+    if !cfg!(feature = "std") && (struct_options.env.is_some() || struct_options.clap) {
+        return Err(syn::Error::new_spanned(&item.ident, "`env` and `clap` need the `std` feature of `struct-builder`"));
+    }
+
+    if !cfg!(feature = "alloc") && struct_options.set_by_name {
+        return Err(syn::Error::new_spanned(&item.ident, "`set_by_name` needs the `alloc` feature of `struct-builder`"));
+    }
+
+    if !struct_options.profiles.is_empty() && !matches!(item.fields, Fields::Named(_)) {
+        return Err(syn::Error::new_spanned(&struct_options.profiles[0].name, "profiles need a struct with named fields"));
+    }
+
+    if !struct_options.presets.is_empty() && !matches!(item.fields, Fields::Named(_)) {
+        return Err(syn::Error::new_spanned(&struct_options.presets[0].name, "presets need a struct with named fields"));
+    }
+
+    for (i, profile) in struct_options.profiles.iter().enumerate() {
+        let clash = struct_options.profiles[..i]
+            .iter()
+            .find(|other| other.builder_fn() == profile.builder_fn() || other.params(&item.ident) == profile.params(&item.ident));
+        if let Some(other) = clash {
+            return Err(syn::Error::new_spanned(
+                &profile.name,
+                format!("profile `{}` clashes with profile `{}`", profile.name, other.name)
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a field's `#[builder(...)]` options against the struct's, as the field is analysed.
+pub fn validate_field(item: &ItemStruct, struct_options: &StructOptions, field: &Field, field_options: &FieldOptions) -> syn::Result<()> {
+    if struct_options.const_fn {
+        if let Some(Skip::Default) = field_options.skip {
+            return Err(syn::Error::new_spanned(field, "skipped fields need a value, `skip = expr`, in a `const` builder"));
+        }
+    }
+
+    if field_options.flatten.is_some() {
+        if !matches!(item.fields, Fields::Named(_)) {
+            return Err(syn::Error::new_spanned(field, "`flatten` needs a struct with named fields"));
+        }
+        if struct_options.const_fn || struct_options.env.is_some() || struct_options.clap {
+            return Err(syn::Error::new_spanned(field, "`flatten` can't be combined with `const`, `env` or `clap`"));
+        }
+        if !is_required(field) || nested_builder_type(&field.ty).is_none() {
+            return Err(syn::Error::new_spanned(&field.ty, "`flatten` needs a field type that's a path to a builder struct"));
+        }
+    }
+
+    if field_options.env == Some(false) && is_required(field) && !field_options.is_skipped() {
+        return Err(syn::Error::new_spanned(field, "`env = false` needs an optional field, as `from_env` reads every required field"));
+    }
+
+    if struct_options.patch && field_options.has_setter() && !is_required(field) && is_serde_flatten(field) {
+        return Err(syn::Error::new_spanned(field, "`#[serde(flatten)]` on an `Option` field can't be carried over to the patch, which couldn't tell an unset field from `None`"));
+    }
+
+    if field_options.convert.is_some() && field_options.params_type.is_none() {
+        return Err(syn::Error::new_spanned(field, "`convert` and `try_convert` need a `params_type`"));
+    }
+
+    if !cfg!(feature = "alloc") && field_options.is_fallible() {
+        return Err(syn::Error::new_spanned(field, "`try_convert` needs the `alloc` feature of `struct-builder`"));
+    }
+
+    if field_options.params_type.is_some() {
+        if struct_options.const_fn || struct_options.env.is_some() || struct_options.clap {
+            return Err(syn::Error::new_spanned(field, "`params_type` can't be combined with `const`, `env` or `clap`"));
+        }
+        if !is_required(field) || field_options.is_skipped() || field_options.flatten.is_some() {
+            return Err(syn::Error::new_spanned(field, "`params_type` needs a required field that isn't skipped or flattened"));
+        }
+    }
+
+    if field_options.boxed {
+        if struct_options.const_fn {
+            return Err(syn::Error::new_spanned(field, "`boxed` can't be combined with `const`"));
+        }
+        if smart_pointer(option_inner_type(&field.ty).unwrap_or(&field.ty)).is_none() {
+            return Err(syn::Error::new_spanned(&field.ty, "`boxed` needs a `Box`, `Arc` or `Rc` field type, or an `Option` of one"));
+        }
+    }
+
+    if field_options.nested && nested_builder_type(&field.ty).is_none() {
+        return Err(syn::Error::new_spanned(&field.ty, "`nested` needs a field type that's a path to a builder struct, or an `Option` of one"));
+    }
+    Ok(())
+}
+
+/// The builder's own methods, which no preset may be named after.
+const RESERVED_BUILDER_FN_NAMES: [&str; 8] = ["build", "modify", "apply", "apply_env", "set_by_name", "apply_pairs", "from_env", "from_params"];
+
+/// The names taken so far by the fields and the builder's methods, which each field is checked against as it's analysed.
+/// No two fields may get the same name for `set_by_name`, `env` variables and the fields' metadata, e.g. a field `depth`
+/// and another with `name = "depth"`, and no two builder methods may get the same name, e.g. `with_foo_if` from both
+/// `foo` and `foo_if`, or a preset named `build`. Fields whose `#[cfg]`s can't hold together, like `cfg(x)` and
+/// `cfg(not(x))`, are never compiled together, so they may share names.
+pub struct TakenNames<'a> {
+    field_names: HashMap<String, Vec<&'a Field>>,
+    fn_names: HashMap<String, Vec<(String, Option<&'a Field>)>>
+}
+
+impl<'a> TakenNames<'a> {
+    /// The names of the builder's own methods and its presets.
+    pub fn new(struct_options: &StructOptions) -> syn::Result<Self> {
+        let mut fn_names: HashMap<String, Vec<(String, Option<&Field>)>> = RESERVED_BUILDER_FN_NAMES
+            .iter()
+            .map(|fn_name| (fn_name.to_string(), vec![("the builder".to_string(), None)]))
+            .collect();
+
+        for preset in &struct_options.presets {
+            let fn_name = preset.name.unraw().to_string();
+            if let Some((owner, _)) = fn_names.get(&fn_name).and_then(|owners| owners.first()) {
+                return Err(syn::Error::new_spanned(&preset.name, format!("preset `{}` clashes with the method of {}", fn_name, owner)));
+            }
+            fn_names.insert(fn_name.clone(), vec![(format!("preset `{}`", fn_name), None)]);
+        }
+
+        Ok(Self { field_names: HashMap::new(), fn_names })
+    }
+
+    /// Takes the names of the field at `index` and of its builder methods.
+    pub fn take(&mut self, index: usize, field: &'a Field, field_options: &FieldOptions) -> syn::Result<()> {
+        let clashes = |other: &Field| !cfg_disjoint(field, other);
+
+        let field_name = field_options.field_name(index, field);
+        let others = self.field_names.entry(field_name.clone()).or_default();
+        if others.iter().any(|other| clashes(other)) {
+            return Err(syn::Error::new_spanned(field, format!("another field is already named `{}`", field_name)));
+        }
+        others.push(field);
+
+        if !field_options.has_setter() {
+            return Ok(());
+        }
+
+        let name = match &field.ident {
+            Some(ident) => ident.unraw().to_string(),
+            None => index.to_string()
+        };
+
+        let mut fn_names = vec![
+            format!("with_{}", name),
+            format!("map_{}", name),
+            format!("with_{}_if", name),
+            format!("maybe_with_{}", name)
+        ];
+        if !field_options.boxed && is_trait_object_pointer(&field.ty) {
+            fn_names.push(format!("with_{}_boxed", name));
+        }
+        if field_options.nested {
+            fn_names.push(format!("with_{}_builder", name));
+            fn_names.push(format!("update_{}", name));
+        }
+        if let Some(flattened) = &field_options.flatten {
+            fn_names.extend(flattened.iter().filter_map(|nested_field| nested_field.ident.as_ref()).map(|ident| format!("with_{}", ident.unraw())));
+        }
+
+        for fn_name in fn_names {
+            let owners = self.fn_names.entry(fn_name.clone()).or_default();
+            if let Some((owner, _)) = owners.iter().find(|(_, other)| other.is_none_or(clashes)) {
+                return Err(syn::Error::new_spanned(
+                    field.ident.as_ref().map(ToTokens::to_token_stream).unwrap_or_else(|| field.ty.to_token_stream()),
+                    format!("builder method `{}` of field `{}` clashes with the one of {}", fn_name, name, owner)
+                ));
+            }
+            owners.push((format!("field `{}`", name), Some(field)));
+        }
+        Ok(())
+    }
+}
+
+/// Check a profile's fields, once all the fields are analysed.
+pub fn validate_profile<'a>(profile: &ProfileOptions, fields: impl Iterator<Item = (&'a Field, &'a FieldContext)> + Clone) -> syn::Result<()> {
+    let name = &profile.name;
+    for required in &profile.required {
+        let field = fields.clone().find(|(field, _)| field.ident.as_ref() == Some(required));
+        match field {
+            None => return Err(syn::Error::new_spanned(required, format!("unknown field `{}` in profile `{}`", required, name))),
+            Some((_, field_ctx)) if field_ctx.options.is_skipped() =>
+                return Err(syn::Error::new_spanned(required, format!("skipped field `{}` can't be required in profile `{}`", required, name))),
+            Some(_) => ()
+        }
+    }
+
+    for (field, field_ctx) in fields {
+        if field_ctx.required && !profile.requires(field) {
+            let ident = field.ident.as_ref().expect("named field missing ident");
+            return Err(syn::Error::new_spanned(name, format!("profile `{}` needs required field `{}` in `required(..)`", name, ident)));
+        }
+    }
+    Ok(())
+}
+
+/// Check a preset's fields, once all the fields are analysed.
+pub fn validate_preset<'a>(preset: &PresetOptions, fields: impl Iterator<Item = (&'a Field, &'a FieldContext)> + Clone) -> syn::Result<()> {
+    let name = &preset.name;
+    for (ident, _) in &preset.values {
+        let field = fields.clone().find(|(field, _)| field.ident.as_ref() == Some(ident));
+        match field {
+            None => return Err(syn::Error::new_spanned(ident, format!("unknown field `{}` in preset `{}`", ident, name))),
+            Some((_, field_ctx)) if field_ctx.required =>
+                return Err(syn::Error::new_spanned(ident, format!("required field `{}` comes from the params struct, so it can't be in preset `{}`", ident, name))),
+            Some(_) => ()
+        }
+    }
+    Ok(())
+}
+
+/// Remove the `#[builder(...)]` attributes from the struct and its fields, as they are only meaningful to this macro.
+pub fn strip_builder_attributes(mut item: ItemStruct) -> ItemStruct {
+    item.attrs.retain(|attr| !is_builder_attribute(attr));
+    for field in item.fields.iter_mut() {
+        field.attrs.retain(|attr| !is_builder_attribute(attr));
+    }
+    item
+}
+
+#[inline]
+pub fn is_builder_attribute(attr: &Attribute) -> bool {
+    attr.path().is_ident(BUILDER_ATTRIBUTE_NAME)
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path_type) => path_type.path.segments
+            .last()
+            .map(|seg| seg.ident == "PhantomData")
+            .unwrap_or(false),
+
+        _ => false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{FieldOptions, Skip, StructOptions};
+    use crate::struct_builder::BuilderContext;
+    use quote::{format_ident, ToTokens};
+    use syn::{parse_quote, Field, ItemStruct};
+
+    /// The options are checked as the struct is analysed.
+    fn validate(item: &ItemStruct) -> syn::Result<()> {
+        BuilderContext::new(item).map(|_| ())
+    }
+
+    #[test]
+    fn test_struct_without_options() {
+        let item: ItemStruct = parse_quote! { pub struct MyStruct; };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+
+        assert!(!options.const_fn);
+    }
+
+    #[test]
+    fn test_struct_with_const() {
+        let item: ItemStruct = parse_quote! { #[builder(const)] pub struct MyStruct; };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+
+        assert!(options.const_fn);
+    }
+
+    #[test]
+    fn test_struct_with_env() {
+        let item: ItemStruct = parse_quote! { #[builder(env)] pub struct MyStruct; };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+
+        assert_eq!(options.env.unwrap().prefix, "");
+    }
+
+    #[test]
+    fn test_struct_with_env_prefix() {
+        let item: ItemStruct = parse_quote! { #[builder(patch, env(prefix = "APP_"))] pub struct MyStruct; };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+
+        assert!(options.patch);
+        assert_eq!(options.env.unwrap().prefix, "APP_");
+    }
+
+    #[test]
+    fn test_struct_with_profiles() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(profile(name = "admin", required(email, role)), profile(name = "self_signup", required(email)))]
+            pub struct User;
+        };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+        let subject = format_ident!("User");
+
+        assert_eq!(options.profiles.len(), 2);
+        assert_eq!(options.profiles[0].required, vec![format_ident!("email"), format_ident!("role")]);
+        assert_eq!(options.profiles[0].builder_fn(), "admin_builder");
+        assert_eq!(options.profiles[1].params(&subject), "UserSelfSignupParams");
+    }
+
+    #[test]
+    fn test_struct_with_params_field_vis() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(params_field_vis = "pub(crate)")]
+            pub struct MyStruct;
+        };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+
+        assert_eq!(options.params_field_vis().to_token_stream().to_string(), "pub (crate)");
+        assert_eq!(StructOptions::default().params_field_vis().to_token_stream().to_string(), "pub");
+    }
+
+    #[test]
+    fn test_struct_with_presets() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(preset(name = "local_dev", port = 8080, host = Some("localhost".into())))]
+            pub struct Config;
+        };
+
+        let options = StructOptions::parse(&item.attrs).unwrap();
+        let fields = options.presets[0].values.iter().map(|(ident, _)| ident.clone()).collect::<Vec<_>>();
+
+        assert_eq!(options.presets.len(), 1);
+        assert_eq!(options.presets[0].name, "local_dev");
+        assert_eq!(fields, vec![format_ident!("port"), format_ident!("host")]);
+    }
+
+    #[test]
+    fn test_field_without_options() {
+        let field: Field = parse_quote! { pub value: String };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(options.skip.is_none());
+    }
+
+    #[test]
+    fn test_field_with_skip() {
+        let field: Field = parse_quote! { #[builder(skip)] pub value: String };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(matches!(options.skip, Some(Skip::Default)));
+    }
+
+    #[test]
+    fn test_field_with_skip_value() {
+        let field: Field = parse_quote! { #[builder(skip = 1 + 2)] pub value: u8 };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        match options.skip {
+            Some(Skip::Value(expr)) => assert_eq!(expr.to_token_stream().to_string(), "1 + 2"),
+            _ => panic!("expected skip value")
+        }
+    }
+
+    #[test]
+    fn test_field_with_setter_options() {
+        let field: Field = parse_quote! { #[builder(setter(skip, vis = "pub(crate)"))] pub value: String };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(options.setter.skip);
+        assert!(!options.has_setter());
+        assert_eq!(options.setter_vis().to_token_stream().to_string(), "pub (crate)");
+    }
+
+    #[test]
+    fn test_field_with_non_const_setter() {
+        let field: Field = parse_quote! { #[builder(setter(const = false))] pub value: Option<String> };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert_eq!(options.setter.const_fn, Some(false));
+        assert!(!options.has_const_setter(true));
+        assert!(FieldOptions::default().has_const_setter(true));
+    }
+
+    #[test]
+    fn test_field_with_nested() {
+        let field: Field = parse_quote! { #[builder(nested)] pub address: Address };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(options.nested);
+    }
+
+    #[test]
+    fn test_field_with_params_type() {
+        let field: Field = parse_quote! { #[builder(params_type = u32, try_convert)] pub port: NonZeroU32 };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert_eq!(options.params_field(&field).ty.to_token_stream().to_string(), "u32");
+        assert!(options.is_fallible());
+    }
+
+    #[test]
+    fn test_field_with_boxed() {
+        let boxed: Field = parse_quote! { #[builder(boxed)] pub handler: Box<dyn Handler> };
+        let unboxed: Field = parse_quote! { pub handler: Box<dyn Handler> };
+
+        assert!(FieldOptions::parse(&boxed).unwrap().boxed);
+        assert!(!FieldOptions::parse(&unboxed).unwrap().boxed);
+    }
+
+    #[test]
+    fn test_field_with_flatten() {
+        let field: Field = parse_quote! { #[builder(flatten(created_by: String, note: Option<String>))] pub audit: AuditInfo };
+
+        let options = FieldOptions::parse(&field).unwrap();
+        let names = options.flatten
+            .unwrap()
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["created_by", "note"]);
+    }
+
+    #[test]
+    fn test_field_without_set_by_name() {
+        let field: Field = parse_quote! { #[builder(set_by_name = false)] pub tags: Vec<String> };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(options.has_setter());
+        assert!(!options.is_set_by_name());
+    }
+
+    #[test]
+    fn test_field_without_env() {
+        let field: Field = parse_quote! { #[builder(env = false)] pub tags: Option<Vec<String>> };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(!options.is_read_from_env());
+    }
+
+    #[test]
+    fn test_field_name() {
+        let field: Field = parse_quote! { pub r#type: String };
+        let named_field: Field = parse_quote! { #[builder(name = "kind")] pub r#type: String };
+        let unnamed_field: Field = parse_quote! { pub String };
+
+        assert_eq!(FieldOptions::parse(&field).unwrap().field_name(0, &field), "type");
+        assert_eq!(FieldOptions::parse(&named_field).unwrap().field_name(0, &named_field), "kind");
+        assert_eq!(FieldOptions::parse(&unnamed_field).unwrap().field_name(2, &unnamed_field), "2");
+    }
+
+    #[test]
+    fn test_phantom_data_field_is_skipped() {
+        let field: Field = parse_quote! { marker: std::marker::PhantomData<T> };
+
+        let options = FieldOptions::parse(&field).unwrap();
+
+        assert!(options.is_skipped());
+    }
+
+    #[test]
+    fn test_validate_unsupported_option() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(unknown)]
+                pub value: String
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "unsupported builder field option");
+    }
+
+    #[test]
+    fn test_validate_clap_with_unnamed_fields() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(clap)]
+            pub struct MyStruct(pub String);
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`clap` arguments need a struct with named fields");
+    }
+
+    #[test]
+    fn test_validate_const_with_default_skip() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(const)]
+            pub struct MyStruct {
+                #[builder(skip)]
+                pub value: u8
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "skipped fields need a value, `skip = expr`, in a `const` builder");
+    }
+
+    #[test]
+    fn test_validate_nested_without_path_type() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(nested)]
+                pub value: (u8, u8)
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`nested` needs a field type that's a path to a builder struct, or an `Option` of one");
+    }
+
+    #[test]
+    fn test_validate_flatten_without_fields() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(flatten)]
+                pub audit: AuditInfo
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`flatten` needs the nested struct's fields, `flatten(field: Type, ..)`");
+    }
+
+    #[test]
+    fn test_validate_flatten_with_env() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(env)]
+            pub struct MyStruct {
+                #[builder(flatten(created_by: String))]
+                pub audit: AuditInfo
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`flatten` can't be combined with `const`, `env` or `clap`");
+    }
+
+    #[test]
+    fn test_validate_profile_without_required_field() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(profile(name = "admin", required(email)))]
+            pub struct User {
+                pub email: String,
+                pub role: String
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "profile `admin` needs required field `role` in `required(..)`");
+    }
+
+    #[test]
+    fn test_validate_profile_with_unknown_field() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(profile(name = "admin", required(email, rank)))]
+            pub struct User {
+                pub email: String
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "unknown field `rank` in profile `admin`");
+    }
+
+    #[test]
+    fn test_validate_preset_with_unknown_field() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(preset(name = "local_dev", prot = 8080))]
+            pub struct Config {
+                pub port: Option<u16>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "unknown field `prot` in preset `local_dev`");
+    }
+
+    #[test]
+    fn test_validate_preset_with_required_field() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(preset(name = "local_dev", host = "localhost".into()))]
+            pub struct Config {
+                pub host: String
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "required field `host` comes from the params struct, so it can't be in preset `local_dev`");
+    }
+
+    #[test]
+    fn test_validate_required_field_without_env() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(env)]
+            pub struct Server {
+                #[builder(env = false)]
+                pub tags: Vec<String>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`env = false` needs an optional field, as `from_env` reads every required field");
+    }
+
+    #[test]
+    fn test_validate_convert_without_params_type() {
+        let item: ItemStruct = parse_quote! {
+            pub struct Server {
+                #[builder(try_convert)]
+                pub port: NonZeroU32
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`convert` and `try_convert` need a `params_type`");
+    }
+
+    #[test]
+    fn test_validate_params_type_with_optional_field() {
+        let item: ItemStruct = parse_quote! {
+            pub struct Server {
+                #[builder(params_type = u32, try_convert)]
+                pub port: Option<NonZeroU32>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`params_type` needs a required field that isn't skipped or flattened");
+    }
+
+    #[test]
+    fn test_validate_patch_with_flattened_optional_field() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(patch)]
+            pub struct MyStruct {
+                #[serde(flatten)]
+                pub foo: Option<Foo>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`#[serde(flatten)]` on an `Option` field can't be carried over to the patch, which couldn't tell an unset field from `None`");
+    }
+
+    #[test]
+    fn test_validate_params_type_with_env() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(env)]
+            pub struct Server {
+                #[builder(params_type = &'static str)]
+                pub host: String
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`params_type` can't be combined with `const`, `env` or `clap`");
+    }
+
+    #[test]
+    fn test_validate_boxed_without_pointer_type() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[builder(boxed)]
+                pub config: Config
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "`boxed` needs a `Box`, `Arc` or `Rc` field type, or an `Option` of one");
+    }
+
+    #[test]
+    fn test_validate_duplicate_field_names() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub depth: u8,
+                #[builder(name = "depth")]
+                pub max_depth: Option<u8>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "another field is already named `depth`");
+    }
+
+    #[test]
+    fn test_validate_clashing_builder_fn_names() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub foo: u8,
+                pub foo_if: Option<bool>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "builder method `with_foo_if` of field `foo_if` clashes with the one of field `foo`");
+    }
+
+    #[test]
+    fn test_validate_builder_fn_names_of_fields_with_other_cfgs() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[cfg(feature = "a")]
+                pub foo: u8,
+                #[cfg(not(feature = "a"))]
+                pub foo_if: Option<bool>,
+                #[builder(setter(skip))]
+                pub bar: u8,
+                pub bar_if: u8
+            }
+        };
+
+        assert!(validate(&item).is_ok());
+    }
+
+    #[test]
+    fn test_validate_clashing_boxed_builder_fn_names() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                pub handler: Box<dyn Handler>,
+                pub handler_boxed: Option<u8>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "builder method `with_handler_boxed` of field `handler_boxed` clashes with the one of field `handler`");
+    }
+
+    #[test]
+    fn test_validate_clashing_builder_fn_names_of_fields_with_other_cfgs() {
+        let item: ItemStruct = parse_quote! {
+            pub struct MyStruct {
+                #[cfg(feature = "a")]
+                pub foo: u8,
+                #[cfg(feature = "b")]
+                pub foo_if: Option<bool>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "builder method `with_foo_if` of field `foo_if` clashes with the one of field `foo`");
+    }
+
+    #[test]
+    fn test_validate_preset_with_reserved_name() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(preset(name = "build", foo = Some(1)))]
+            pub struct MyStruct {
+                pub foo: Option<u8>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "preset `build` clashes with the method of the builder");
+    }
+
+    #[test]
+    fn test_validate_clashing_presets() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(preset(name = "local", foo = Some(1)), preset(name = "local", foo = Some(2)))]
+            pub struct MyStruct {
+                pub foo: Option<u8>
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "preset `local` clashes with the method of preset `local`");
+    }
+
+    #[test]
+    fn test_validate_clashing_profiles() {
+        let item: ItemStruct = parse_quote! {
+            #[builder(profile(name = "admin", required(foo)), profile(name = "admin", required(foo)))]
+            pub struct MyStruct {
+                pub foo: u8
+            }
+        };
+
+        let error = validate(&item).unwrap_err();
+
+        assert_eq!(error.to_string(), "profile `admin` clashes with profile `admin`");
+    }
+}
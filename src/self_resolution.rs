@@ -0,0 +1,70 @@
+use crate::struct_builder::GenericsContext;
+use syn::visit_mut::VisitMut;
+use syn::{parse_quote, ItemStruct, Type, TypePath};
+
+struct ReplaceSelf {
+    subject: Type
+}
+
+impl VisitMut for ReplaceSelf {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => *ty = self.subject.clone(),
+            _ => syn::visit_mut::visit_type_mut(self, ty)
+        }
+    }
+}
+
+/// Replace `Self` in the field types and where clause with the struct's own type, since the generated items using them
+/// are other types (`Self` in the params struct is the params struct).
+pub fn resolve_self(item: &ItemStruct) -> ItemStruct {
+    let ident = &item.ident;
+    let GenericsContext { generics_expr, .. } = item.into();
+    let mut replace_self = ReplaceSelf {
+        subject: parse_quote! { #ident #generics_expr }
+    };
+
+    let mut item = item.clone();
+    for field in item.fields.iter_mut() {
+        replace_self.visit_type_mut(&mut field.ty);
+    }
+    if let Some(where_clause) = item.generics.where_clause.as_mut() {
+        replace_self.visit_where_clause_mut(where_clause);
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::self_resolution::resolve_self;
+    use quote::ToTokens;
+    use syn::{parse_quote, ItemStruct};
+
+    macro_rules! test_cases {
+        ($($name:tt| $item:tt | $expected:tt),*$(,)?) => {
+            $(
+                #[test]
+                fn $name() {
+                    let item: ItemStruct = parse_quote! $item ;
+                    let expected: ItemStruct = parse_quote! $expected ;
+
+                    assert_eq!(
+                        resolve_self(&item).to_token_stream().to_string(),
+                        expected.to_token_stream().to_string()
+                    );
+                }
+            )*
+        };
+    }
+
+    test_cases! {
+        test_no_self        |{ struct Leaf { value: u8 }                     }|{ struct Leaf { value: u8 }                        },
+        test_self           |{ struct Node { next: Box<Self> }               }|{ struct Node { next: Box<Node> }                  },
+        test_self_generics  |{ struct Tree<'a, T> { children: Vec<Self>, value: &'a T } }|{ struct Tree<'a, T> { children: Vec<Tree<'a, T>>, value: &'a T } },
+        test_nested_self    |{ struct List { next: Option<Box<Self>> }       }|{ struct List { next: Option<Box<List>> }         },
+        test_self_reference |{ struct Link<'a> { prev: Option<&'a Self> }    }|{ struct Link<'a> { prev: Option<&'a Link<'a>> }  },
+        test_unnamed_self   |{ struct Pair(Box<Self>, [Self; 0]);             }|{ struct Pair(Box<Pair>, [Pair; 0]);               },
+        test_where_self     |{ struct Wrap<T> where Self: Sized { value: T } }|{ struct Wrap<T> where Wrap<T>: Sized { value: T } },
+        test_assoc_self     |{ struct Assoc<T: Tr> { value: <Self as Tr>::Out, t: T } }|{ struct Assoc<T: Tr> { value: <Assoc<T> as Tr>::Out, t: T } },
+    }
+}
@@ -1,31 +1,58 @@
-use crate::components::{is_required, BuilderStruct, ImplBuilderFns, ImplFromBuilderForSubject, ImplFromParamsForSubject, ImplFromSubjectForBuilder, ImplSubjectFnBuilder, ParamsStruct};
+use crate::components::{is_required, params_fields, ArgsStruct, BuilderStruct, ExternCrateAlloc, ExternCrateStd, ImplBuilderFns, ImplBuilderNestedFns, ImplBuilderPresetFns, ImplBuilderUpdateFns, ImplEnvFns, ImplFieldMetadata, ImplFromArgsForSubject, ImplFromBuilderForSubject, ImplFromParamsForSubject, ImplFromSubjectForBuilder, ImplPatchFns, ImplSetByNameFns, ImplSubjectFnBuilder, ParamsErrorStruct, ParamsStruct, PatchStruct};
 use crate::generic_resolution::field_has_generic;
+use crate::group_resolution::resolve_groups;
+use crate::options::{is_builder_attribute, validate_field, validate_preset, validate_profile, validate_struct, FieldOptions, StructOptions, TakenNames};
+use crate::self_resolution::resolve_self;
 use proc_macro2::TokenStream;
 use quote::{format_ident, ToTokens};
 use syn::punctuated::Punctuated;
-use syn::{parse_quote, Attribute, ConstParam, GenericParam, Generics, Ident, ItemStruct, LifetimeParam, Token, TypeParam, WhereClause};
+use syn::{parse_quote, Attribute, ConstParam, Field, GenericParam, Fields, Generics, Ident, ItemStruct, LifetimeParam, Token, TypeParam, WhereClause};
 
 const PARAMS_ARGUMENT_NAME: &str = "params";
 const BUILDER_SUBJECT_FIELD_NAME: &str = "inner";
 
 pub struct StructBuilder(pub ItemStruct);
 
+/// Everything the components generate from, analysed once per struct and borrowed by each of them.
 pub struct BuilderContext {
     pub subject: Ident,
     pub params: Ident,
+    pub params_error: Ident,
     pub params_argument: Ident,
     pub builder: Ident,
     pub builder_subject_field: Ident,
+    pub patch: Ident,
+    pub env_error: Ident,
+    pub args: Ident,
+    pub set_field_error: Ident,
+    pub field_info: Ident,
+    pub alloc: Ident,
+    pub std: Ident,
+    pub options: StructOptions,
     pub attributes: AttributesContext,
     pub generics: GenericsContext,
-    pub fields_metadata: FieldsMetadata
+    pub fields_metadata: FieldsMetadata,
+    pub fields: Fields,
+    pub field_contexts: Vec<FieldContext>,
+    pub params_fields: Vec<Field>
+}
+
+/// A field's options and the flags derived from them, analysed once per field.
+pub struct FieldContext {
+    pub options: FieldOptions,
+    /// Whether the field is in the params struct, i.e. it's neither an `Option` nor skipped.
+    pub required: bool,
+    /// Whether the field's type uses the struct's generic params.
+    pub generic: bool
 }
 
 pub struct AttributesContext {
     pub outer_attrs: Vec<Attribute>,
+    pub non_exhaustive: bool
 }
 
 pub struct GenericsContext {
+    pub generics_decl: Generics,
     pub generics_def: Generics,
     pub generics_expr: Generics,
     pub where_clause: Option<WhereClause>
@@ -35,28 +62,89 @@ pub struct FieldsMetadata {
     pub required_fields_count: usize,
     pub optional_fields_count: usize,
     pub generic_required_fields_count: usize,
-    pub generic_optional_fields_count: usize
+    pub generic_optional_fields_count: usize,
+    pub skipped_fields_count: usize
 }
 
-impl From<&ItemStruct> for BuilderContext {
-    fn from(item: &ItemStruct) -> Self {
-        BuilderContext {
+impl BuilderContext {
+    /// Analyses the struct, parsing the options of the struct and each field once and checking them as it goes, so errors
+    /// are reported before any code is generated.
+    pub fn new(item: &ItemStruct) -> syn::Result<Self> {
+        let options = StructOptions::parse(&item.attrs)?;
+        validate_struct(item, &options)?;
+
+        let mut taken_names = TakenNames::new(&options)?;
+        let field_contexts = item.fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let field_ctx = FieldContext::new(&item.generics, field)?;
+                validate_field(item, &options, field, &field_ctx.options)?;
+                taken_names.take(i, field, &field_ctx.options)?;
+                Ok(field_ctx)
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        for profile in &options.profiles {
+            validate_profile(profile, item.fields.iter().zip(&field_contexts))?;
+        }
+        for preset in &options.presets {
+            validate_preset(preset, item.fields.iter().zip(&field_contexts))?;
+        }
+
+        let fields_metadata = FieldsMetadata::count(&item.generics, item.fields.iter().zip(&field_contexts));
+        let params_fields = params_fields(item.fields.iter().zip(&field_contexts), &options.params_field_vis());
+
+        Ok(BuilderContext {
             subject: format_ident!("{}", &item.ident),
             params: format_ident!("{}Params", &item.ident),
+            params_error: format_ident!("{}ParamsError", &item.ident),
             params_argument: format_ident!("{}", PARAMS_ARGUMENT_NAME),
             builder: format_ident!("{}Builder", &item.ident),
             builder_subject_field: format_ident!("{}", BUILDER_SUBJECT_FIELD_NAME),
+            patch: format_ident!("{}Patch", &item.ident),
+            env_error: format_ident!("{}EnvError", &item.ident),
+            args: format_ident!("{}Args", &item.ident),
+            set_field_error: format_ident!("{}SetFieldError", &item.ident),
+            field_info: format_ident!("{}FieldInfo", &item.ident),
+            alloc: format_ident!("__{}_alloc", &item.ident),
+            std: format_ident!("__{}_std", &item.ident),
+            options,
             attributes: item.into(),
             generics: item.into(),
-            fields_metadata: item.into()
-        }
+            fields_metadata,
+            fields: item.fields.clone(),
+            field_contexts,
+            params_fields
+        })
+    }
+
+    /// The struct's fields, each with its analysis.
+    pub fn analysed_fields(&self) -> impl Iterator<Item = (&Field, &FieldContext)> {
+        self.fields.iter().zip(&self.field_contexts)
+    }
+}
+
+impl FieldContext {
+    /// Analyses a field of a struct with the given generics.
+    pub fn new(generics: &Generics, field: &Field) -> syn::Result<Self> {
+        let options = FieldOptions::parse(field)?;
+        let required = is_required(field) && !options.is_skipped();
+        let generic = field_has_generic(generics, field);
+        Ok(Self { options, required, generic })
     }
 }
 
 impl From<&ItemStruct> for AttributesContext {
     fn from(item: &ItemStruct) -> Self {
-        let outer_attrs = item.attrs.to_owned();
-        Self { outer_attrs } 
+        // Generated structs are built downstream, which `#[non_exhaustive]` would prevent
+        let outer_attrs = item.attrs
+            .iter()
+            .filter(|attr| !is_builder_attribute(attr) && !attr.path().is_ident("non_exhaustive"))
+            .cloned()
+            .collect();
+        let non_exhaustive = item.attrs.iter().any(|attr| attr.path().is_ident("non_exhaustive"));
+        Self { outer_attrs, non_exhaustive }
     }
 }
 
@@ -64,9 +152,25 @@ impl From<&ItemStruct> for GenericsContext {
     fn from(item: &ItemStruct) -> Self {
         let generics = &item.generics;
 
-        // Definitions of generic params, including bounds
-        let mut generics_def = generics.to_owned();
-        generics_def.where_clause = None;
+        // Declarations of generic params for generated types, including bounds and defaults
+        let mut generics_decl = generics.to_owned();
+        generics_decl.where_clause = None;
+
+        // Definitions of generic params for impl blocks, including bounds, but without the defaults they don't allow
+        let mut generics_def = generics_decl.to_owned();
+        for param in generics_def.params.iter_mut() {
+            match param {
+                GenericParam::Type(type_param) => {
+                    type_param.eq_token = None;
+                    type_param.default = None;
+                },
+                GenericParam::Const(const_param) => {
+                    const_param.eq_token = None;
+                    const_param.default = None;
+                },
+                GenericParam::Lifetime(_) => ()
+            }
+        }
 
         // Expression of generic params, just the identifiers (no bounds)
         let mut generics_expr = generics.to_owned();
@@ -80,8 +184,9 @@ impl From<&ItemStruct> for GenericsContext {
                 GenericParam::Type(TypeParam { ident, .. }) =>
                     GenericParam::Type(parse_quote! { #ident }),
                 
+                // A const param's argument is just its identifier, which only parses as a type param
                 GenericParam::Const(ConstParam { ident, .. }) =>
-                    GenericParam::Const(parse_quote! { #ident })
+                    GenericParam::Type(parse_quote! { #ident })
             })
             .collect::<Punctuated<GenericParam, Token![,]>>();
         
@@ -89,6 +194,7 @@ impl From<&ItemStruct> for GenericsContext {
         let where_clause = generics.where_clause.to_owned();
         
         GenericsContext {
+            generics_decl,
             generics_def,
             generics_expr,
             where_clause
@@ -96,18 +202,42 @@ impl From<&ItemStruct> for GenericsContext {
     }
 }
 
-impl From<&ItemStruct> for FieldsMetadata {
-    fn from(value: &ItemStruct) -> Self {
+impl FieldsMetadata {
+    /// Counts the given analysed fields of a struct with the given generics.
+    pub fn count<'a>(generics: &Generics, fields: impl IntoIterator<Item = (&'a Field, &'a FieldContext)>) -> Self {
         let mut meta = Self {
             required_fields_count: 0,
             optional_fields_count: 0,
             generic_required_fields_count: 0,
             generic_optional_fields_count: 0,
+            skipped_fields_count: 0,
         };
 
-        for field in &value.fields {
-            let generic = field_has_generic(&value.generics, &field);
-            let required = is_required(&field);
+        for (field, FieldContext { options, required, generic }) in fields {
+            if options.is_skipped() {
+                meta.skipped_fields_count += 1;
+                continue;
+            }
+
+            // A flattened field's own required fields take its place in the params struct
+            if let Some(flattened) = &options.flatten {
+                for nested_field in flattened.iter().filter(|nested_field| is_required(nested_field)) {
+                    if field_has_generic(generics, nested_field) {
+                        meta.generic_required_fields_count += 1;
+                    } else {
+                        meta.required_fields_count += 1;
+                    }
+                }
+                continue;
+            }
+
+            // Required fields are counted as they're declared in the params struct
+            let generic = if options.params_type.is_some() {
+                field_has_generic(generics, &options.params_field(field))
+            } else {
+                *generic
+            };
+            let required = *required;
 
             if generic && required {
                 meta.generic_required_fields_count += 1;
@@ -127,17 +257,91 @@ impl From<&ItemStruct> for FieldsMetadata {
 impl ToTokens for StructBuilder {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self(item) = &self;
-        
+        let ctx = &match BuilderContext::new(&resolve_self(&resolve_groups(item))) {
+            Ok(ctx) => ctx,
+            Err(error) => return error.to_compile_error().to_tokens(tokens)
+        };
+
         let token_streams: Vec<Box<dyn ToTokens>> = vec![
-            Box::new(ImplSubjectFnBuilder::from(item)),
-            Box::new(ParamsStruct::from(item)),
-            Box::new(BuilderStruct::from(item)),
-            Box::new(ImplBuilderFns::from(item)),
-            Box::new(ImplFromBuilderForSubject::from(item)),
-            Box::new(ImplFromParamsForSubject::from(item)),
-            Box::new(ImplFromSubjectForBuilder::from(item)),
+            Box::new(ExternCrateAlloc::from(ctx)),
+            Box::new(ExternCrateStd::from(ctx)),
+            Box::new(ImplSubjectFnBuilder::from(ctx)),
+            Box::new(ParamsStruct::from(ctx)),
+            Box::new(ParamsErrorStruct::from(ctx)),
+            Box::new(BuilderStruct::from(ctx)),
+            Box::new(ImplBuilderFns::from(ctx)),
+            Box::new(ImplBuilderUpdateFns::from(ctx)),
+            Box::new(ImplBuilderNestedFns::from(ctx)),
+            Box::new(ImplBuilderPresetFns::from(ctx)),
+            Box::new(ImplFromBuilderForSubject::from(ctx)),
+            Box::new(ImplFromParamsForSubject::from(ctx)),
+            Box::new(ImplFromSubjectForBuilder::from(ctx)),
+            Box::new(PatchStruct::from(ctx)),
+            Box::new(ImplPatchFns::from(ctx)),
+            Box::new(ImplEnvFns::from(ctx)),
+            Box::new(ArgsStruct::from(ctx)),
+            Box::new(ImplFromArgsForSubject::from(ctx)),
+            Box::new(ImplSetByNameFns::from(ctx)),
+            Box::new(ImplFieldMetadata::from(ctx)),
         ];
 
-        token_streams.iter().for_each(|ts| ts.to_tokens(tokens));
+        // Each profile has its own params struct and builder function
+        let profile_token_streams = ctx.options.profiles
+            .iter()
+            .flat_map::<[Box<dyn ToTokens>; 2], _>(|profile| [
+                Box::new(ParamsStruct::for_profile(ctx, profile)),
+                Box::new(ImplSubjectFnBuilder::for_profile(ctx, profile))
+            ]);
+
+        token_streams.into_iter().chain(profile_token_streams).for_each(|ts| ts.to_tokens(tokens));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::struct_builder::StructBuilder;
+    use quote::{format_ident, ToTokens};
+    use std::time::{Duration, Instant};
+    use syn::{parse_quote, Field, ItemStruct};
+
+    fn large_generic_struct(field_count: usize) -> ItemStruct {
+        let fields = (0..field_count).map::<Field, _>(|i| {
+            let ident = format_ident!("field_{}", i);
+            match i % 4 {
+                0 => parse_quote! { pub #ident: T },
+                1 => parse_quote! { pub #ident: Option<T> },
+                2 => parse_quote! { pub #ident: String },
+                _ => parse_quote! { pub #ident: Option<u64> }
+            }
+        });
+        parse_quote! {
+            #[builder(patch, set_by_name, env)]
+            pub struct MyStruct<T: Clone> {
+                #(#fields),*
+            }
+        }
+    }
+
+    /// The fastest of a few expansions, including the analysis and checks of the options, to smooth out the machine's load.
+    fn expansion_time(item_struct: &ItemStruct) -> Duration {
+        (0..3)
+            .map(|_| {
+                let start = Instant::now();
+                let tokens = StructBuilder(item_struct.clone()).to_token_stream();
+                let elapsed = start.elapsed();
+                assert!(!tokens.to_string().contains("compile_error"));
+                elapsed
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_expansion_time_of_large_generic_struct() {
+        let half = expansion_time(&large_generic_struct(250));
+        let full = expansion_time(&large_generic_struct(500));
+
+        // Doubling the fields roughly doubles the time if expansion is linear, and would quadruple it if it's quadratic
+        assert!(full < half * 3, "expansion of 500 fields took {:?}, and of 250 fields {:?}", full, half);
     }
 }
@@ -47,3 +47,31 @@ pub fn sample_unnamed_item_struct() -> ItemStruct {
 pub fn sample_unit_item_struct() -> ItemStruct {
     parse_quote! { pub struct MyStruct; }
 }
+
+pub fn sample_skipped_named_item_struct() -> ItemStruct {
+    parse_quote! {
+        pub struct MyStruct<T> {
+            pub required: String,
+            #[builder(skip)]
+            pub cached: u64,
+            #[builder(skip = vec![1, 2])]
+            pub computed: Vec<u8>,
+            pub optional: Option<String>,
+            marker: PhantomData<T>
+        }
+    }
+}
+
+pub fn sample_skipped_unnamed_item_struct() -> ItemStruct {
+    parse_quote! {
+        pub struct MyStruct(
+            #[builder(skip)]
+            u64,
+            pub String,
+            Option<u8>,
+            #[builder(skip = 3)]
+            u8,
+            pub String
+        );
+    }
+}
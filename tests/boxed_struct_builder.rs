@@ -0,0 +1,77 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use struct_builder::builder;
+
+pub trait Handler {
+    fn handle(&self, input: u8) -> u8;
+}
+
+pub struct Doubler;
+
+impl Handler for Doubler {
+    fn handle(&self, input: u8) -> u8 {
+        input * 2
+    }
+}
+
+#[builder]
+pub struct Service {
+    pub name: String,
+    #[builder(boxed)]
+    pub on_error: Box<dyn Fn(u8) -> String + Send>,
+    #[builder(boxed)]
+    pub handler: Option<Arc<dyn Handler>>,
+    #[builder(boxed)]
+    pub limits: Rc<Vec<u8>>,
+    pub fallback: Option<Box<dyn Handler>>
+}
+
+fn existing_handler() -> Box<dyn Handler> {
+    Box::new(Doubler)
+}
+
+fn params() -> ServiceParams {
+    ServiceParams {
+        name: "platypus".to_owned(),
+        on_error: Box::new(|code| format!("error {}", code)),
+        limits: Rc::new(vec![])
+    }
+}
+
+#[test]
+fn test_closure_setter() {
+    let service = Service::builder(params())
+        .with_on_error(|code| format!("failed with {}", code))
+        .build();
+
+    assert_eq!((service.on_error)(42), "failed with 42");
+}
+
+#[test]
+fn test_optional_trait_object_setter() {
+    let service = Service::builder(params())
+        .with_handler(Doubler)
+        .build();
+
+    assert_eq!(service.handler.map(|handler| handler.handle(21)), Some(42));
+}
+
+#[test]
+fn test_trait_object_setter_without_boxed() {
+    let service = Service::builder(params())
+        .with_fallback_boxed(Doubler)
+        .build();
+
+    assert_eq!(service.fallback.map(|handler| handler.handle(3)), Some(6));
+}
+
+#[test]
+fn test_boxed_and_unboxed_setters() {
+    let service = Service::builder(params())
+        .with_limits(vec![1, 2])
+        .with_fallback(Some(existing_handler()))
+        .build();
+
+    assert_eq!(*service.limits, vec![1, 2]);
+    assert_eq!(service.fallback.map(|handler| handler.handle(2)), Some(4));
+}
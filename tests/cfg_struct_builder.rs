@@ -0,0 +1,77 @@
+use struct_builder::builder;
+
+#[builder(patch, set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Server {
+    pub host: String,
+    #[cfg(not(test))]
+    pub metrics_port: u16,
+    #[cfg(test)]
+    pub port: u16,
+    #[cfg(not(test))]
+    pub tracing: Option<String>,
+    pub timeout: Option<u64>
+}
+
+#[builder(patch, set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Pair(
+    #[cfg(not(test))] pub u8,
+    pub String,
+    #[cfg(test)] pub u16,
+    pub Option<bool>
+);
+
+#[test]
+fn test_named_fields() {
+    let server = Server::builder(ServerParams { host: "localhost".to_owned(), port: 80 })
+        .with_timeout(Some(30))
+        .build();
+
+    assert_eq!(server, Server { host: "localhost".to_owned(), port: 80, timeout: Some(30) });
+}
+
+#[test]
+fn test_named_patch_and_set_by_name() {
+    let mut builder = Server::builder(ServerParams { host: "localhost".to_owned(), port: 80 });
+    builder.set_by_name("port", "8080").unwrap();
+
+    assert!(builder.set_by_name("metrics_port", "9000").is_err());
+
+    let mut server = builder.build();
+    server.apply(ServerPatch { host: None, port: None, timeout: Some(Some(5)) });
+
+    assert_eq!(server, Server { host: "localhost".to_owned(), port: 8080, timeout: Some(5) });
+}
+
+#[test]
+fn test_unnamed_fields() {
+    let pair = Pair::builder(PairParams("a".to_owned(), 1))
+        .with_1("b".to_owned())
+        .with_2(2)
+        .with_3(Some(true))
+        .build();
+
+    assert_eq!(pair, Pair("b".to_owned(), 2, Some(true)));
+}
+
+#[test]
+fn test_unnamed_patch_and_set_by_name() {
+    let mut builder = Pair::builder(PairParams("a".to_owned(), 1));
+    builder.set_by_name("2", "3").unwrap();
+    builder.set_by_name("3", "false").unwrap();
+
+    let mut pair = builder.build();
+    let other = Pair("b".to_owned(), 3, None);
+    pair.apply(Pair::diff(&pair, &other));
+
+    assert_eq!(pair, other);
+}
+
+#[test]
+fn test_field_metadata() {
+    assert_eq!(ServerFieldInfo::FIELDS.iter().map(|field| field.name).collect::<Vec<_>>(), ["host", "port", "timeout"]);
+    assert_eq!(Server::REQUIRED_FIELD_COUNT, 2);
+    assert_eq!(Server::OPTIONAL_FIELD_COUNT, 1);
+    assert_eq!(Pair::REQUIRED_FIELD_COUNT, 2);
+}
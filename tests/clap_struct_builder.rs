@@ -0,0 +1,59 @@
+use clap::Parser;
+use struct_builder::builder;
+
+#[builder(clap)]
+#[derive(Debug, PartialEq)]
+pub struct Platypus {
+    /// The platypus' name
+    pub name: String,
+    /// The platypus' age, in years
+    pub age: u8,
+    /// What the platypus is called by friends
+    pub nickname: Option<String>,
+    #[builder(skip)]
+    pub sightings: u32
+}
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    platypus: PlatypusArgs
+}
+
+#[test]
+fn test_subject_from_args() {
+    let cli = Cli::try_parse_from(["platypus", "--name", "Perry", "--age", "3"]).unwrap();
+
+    let platypus = Platypus::from(cli.platypus);
+
+    assert_eq!(platypus, Platypus {
+        name: "Perry".to_owned(),
+        age: 3,
+        nickname: None,
+        sightings: 0
+    });
+}
+
+#[test]
+fn test_subject_from_args_with_optional() {
+    let cli = Cli::try_parse_from(["platypus", "--name", "Perry", "--age", "3", "--nickname", "Agent P"]).unwrap();
+
+    let platypus: Platypus = cli.platypus.into();
+
+    assert_eq!(platypus.nickname, Some("Agent P".to_owned()));
+}
+
+#[test]
+fn test_args_require_params() {
+    let error = Cli::try_parse_from(["platypus", "--name", "Perry"]).err().unwrap();
+
+    assert_eq!(error.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+}
+
+#[test]
+fn test_args_help_from_docs() {
+    let help = <Cli as clap::CommandFactory>::command().render_help().to_string();
+
+    assert!(help.contains("The platypus' name"));
+    assert!(help.contains("What the platypus is called by friends"));
+}
@@ -0,0 +1,49 @@
+use struct_builder::builder;
+
+/// Stands in for a fixed-capacity vector type from another crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayVec<T, const CAP: usize>(pub Vec<T>);
+
+#[builder(patch)]
+#[derive(Debug, PartialEq)]
+pub struct Packet<const N: usize> {
+    pub buf: [u8; N],
+    pub checksum: Option<[u8; 4]>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Queue<T, const CAP: usize> {
+    pub items: ArrayVec<T, CAP>,
+    pub spare: Option<[T; CAP]>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Frame<const N: usize>(pub [u8; N], pub Option<u8>);
+
+#[test]
+fn test_const_generic_array_length() {
+    let packet = Packet::builder(PacketParams { buf: [1, 2, 3] })
+        .with_checksum(Some([0; 4]))
+        .build();
+
+    assert_eq!(packet, Packet { buf: [1, 2, 3], checksum: Some([0; 4]) });
+    assert_eq!(Packet::<3>::GENERIC_REQUIRED_FIELD_COUNT, 1);
+}
+
+#[test]
+fn test_const_generic_path_argument() {
+    let queue = Queue::<u8, 2>::builder(QueueParams { items: ArrayVec(vec![1]) })
+        .with_spare(Some([0, 0]))
+        .build();
+
+    assert_eq!(queue, Queue { items: ArrayVec(vec![1]), spare: Some([0, 0]) });
+}
+
+#[test]
+fn test_unnamed_const_generic() {
+    let frame = Frame::from(FrameParams([7; 2]));
+
+    assert_eq!(frame, Frame([7, 7], None));
+}
@@ -0,0 +1,69 @@
+use struct_builder::builder;
+
+#[builder(const)]
+#[derive(Debug, PartialEq)]
+pub struct ServerConfig {
+    pub host: &'static str,
+    pub port: u16,
+    pub workers: Option<u8>,
+    #[builder(skip = false)]
+    pub verbose: bool
+}
+
+#[builder(const)]
+pub struct Limits(pub u32, pub Option<u32>);
+
+#[builder(const)]
+#[derive(Debug, PartialEq)]
+pub struct Client {
+    pub retries: u8,
+    #[builder(setter(const = false))]
+    pub user_agent: Option<String>
+}
+
+const CLIENT: Client = Client::from_params(ClientParams { retries: 3 });
+
+const LOCAL: ServerConfig = ServerConfig::builder(ServerConfigParams { host: "localhost", port: 8080 })
+    .with_workers(Some(4))
+    .build();
+
+static DEFAULT: ServerConfig = ServerConfig::from_params(ServerConfigParams { host: "0.0.0.0", port: 80 });
+
+const LIMITS: Limits = Limits::builder(LimitsParams(100)).with_1(Some(10)).build();
+
+#[test]
+fn test_const_builder() {
+    assert_eq!(LOCAL, ServerConfig {
+        host: "localhost",
+        port: 8080,
+        workers: Some(4),
+        verbose: false
+    });
+}
+
+#[test]
+fn test_const_from_params() {
+    assert_eq!(DEFAULT, ServerConfig {
+        host: "0.0.0.0",
+        port: 80,
+        workers: None,
+        verbose: false
+    });
+}
+
+#[test]
+fn test_const_unnamed_builder() {
+    assert_eq!(LIMITS.0, 100);
+    assert_eq!(LIMITS.1, Some(10));
+}
+
+#[test]
+fn test_const_builder_with_non_const_setter() {
+    assert_eq!(CLIENT, Client { retries: 3, user_agent: None });
+
+    let client = Client::builder(ClientParams { retries: 3 })
+        .with_user_agent(Some("platypus".to_owned()))
+        .build();
+
+    assert_eq!(client.user_agent, Some("platypus".to_owned()));
+}
@@ -0,0 +1,56 @@
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use struct_builder::builder;
+
+#[builder(preset(name = "verbose", verbose = true))]
+#[derive(Debug, PartialEq)]
+pub struct Server {
+    #[builder(params_type = &'static str)]
+    pub host: String,
+    #[builder(params_type = u32, try_convert)]
+    pub port: NonZeroU32,
+    #[builder(params_type = &'static str, convert = PathBuf::from)]
+    pub root: PathBuf,
+    pub verbose: Option<bool>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Label(#[builder(params_type = &'static str)] String);
+
+#[test]
+fn test_converted_params() {
+    let server = Server::builder(ServerParams { host: "localhost", port: 8080, root: "/srv" })
+        .unwrap()
+        .with_verbose(Some(true))
+        .build();
+
+    assert_eq!(server, Server {
+        host: "localhost".to_owned(),
+        port: NonZeroU32::new(8080).unwrap(),
+        root: PathBuf::from("/srv"),
+        verbose: Some(true)
+    });
+}
+
+#[test]
+fn test_failed_conversion() {
+    let error = Server::try_from(ServerParams { host: "localhost", port: 0, root: "/srv" }).unwrap_err();
+
+    assert_eq!(error.field, "port");
+    assert_eq!(error.to_string(), "invalid value for field `port`: out of range integral type conversion attempted");
+}
+
+#[test]
+fn test_fallible_preset() {
+    let server = ServerBuilder::verbose(ServerParams { host: "localhost", port: 8080, root: "/srv" }).unwrap().build();
+
+    assert_eq!(server.verbose, Some(true));
+}
+
+#[test]
+fn test_unnamed_converted_params() {
+    let label = Label::from(LabelParams("platypus"));
+
+    assert_eq!(label, Label("platypus".to_owned()));
+}
@@ -0,0 +1,37 @@
+use struct_builder::builder;
+
+#[builder(patch)]
+#[derive(Debug, PartialEq)]
+pub struct Buffer<T: Clone = String, const N: usize = 2> {
+    pub items: [T; N],
+    pub label: Option<T>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Wrapper<T = u8>(pub T, pub Option<T>);
+
+#[test]
+fn test_default_generics() {
+    let params: BufferParams = BufferParams { items: ["a".to_owned(), "b".to_owned()] };
+    let builder: BufferBuilder = Buffer::builder(params);
+    let buffer: Buffer = builder.with_label(Some("c".to_owned())).build();
+
+    assert_eq!(buffer.items, ["a".to_owned(), "b".to_owned()]);
+    assert_eq!(buffer.label, Some("c".to_owned()));
+}
+
+#[test]
+fn test_explicit_generics() {
+    let buffer = Buffer::<u8, 3>::builder(BufferParams { items: [1, 2, 3] }).build();
+
+    assert_eq!(buffer, Buffer { items: [1, 2, 3], label: None });
+}
+
+#[test]
+fn test_unnamed_default_generics() {
+    let params: WrapperParams = WrapperParams(1);
+    let wrapper: Wrapper = Wrapper::from(params);
+
+    assert_eq!(wrapper, Wrapper(1, None));
+}
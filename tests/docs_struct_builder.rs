@@ -0,0 +1,34 @@
+// Generated code using deprecated fields mustn't warn where the macro is used
+#![deny(deprecated)]
+
+use struct_builder::builder;
+
+/// A platypus.
+#[builder(patch, env(prefix = "PLATYPUS_"), set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Platypus {
+    /// The platypus' name.
+    pub name: String,
+    #[deprecated(note = "use `name`")]
+    pub nickname: Option<String>
+}
+
+#[test]
+fn test_without_deprecated_field() {
+    let platypus = Platypus::builder(PlatypusParams { name: "Perry".to_owned() })
+        .with_name("Agent P".to_owned())
+        .build();
+
+    assert_eq!(platypus.name, "Agent P");
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_with_deprecated_field() {
+    let platypus = Platypus::builder(PlatypusParams { name: "Perry".to_owned() })
+        .with_nickname(Some("Agent P".to_owned()))
+        .map_nickname(|nickname| nickname.map(|nickname| nickname.to_uppercase()))
+        .build();
+
+    assert_eq!(platypus.nickname, Some("AGENT P".to_owned()));
+}
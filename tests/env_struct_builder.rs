@@ -0,0 +1,107 @@
+use std::env;
+use struct_builder::builder;
+
+#[builder(env(prefix = "PLATYPUS_"))]
+#[derive(Debug, PartialEq)]
+pub struct Platypus {
+    pub name: String,
+    pub age: u8,
+    pub nickname: Option<String>
+}
+
+#[builder(env(prefix = "BURROW_"))]
+#[derive(Debug, PartialEq)]
+pub struct Burrow(pub String, pub Option<u8>);
+
+#[builder(env(prefix = "AGENT_"))]
+#[derive(Debug, PartialEq)]
+pub struct Agent {
+    pub code: u32,
+    pub handler: Option<String>
+}
+
+#[builder(env(prefix = "LAIR_"))]
+#[derive(Debug, PartialEq)]
+pub struct Lair {
+    pub traps: u8
+}
+
+#[builder(env(prefix = "GADGET_"))]
+#[derive(Debug, PartialEq)]
+pub struct Gadget {
+    pub name: String,
+    #[builder(env = false)]
+    pub parts: Option<Vec<String>>
+}
+
+#[test]
+fn test_from_env() {
+    env::set_var("PLATYPUS_NAME", "Perry");
+    env::set_var("PLATYPUS_AGE", "3");
+    env::remove_var("PLATYPUS_NICKNAME");
+
+    let platypus = Platypus::from_env().unwrap();
+
+    assert_eq!(platypus, Platypus {
+        name: "Perry".to_owned(),
+        age: 3,
+        nickname: None
+    });
+}
+
+#[test]
+fn test_unnamed_from_env() {
+    env::set_var("BURROW_0", "Tri-State Area");
+    env::set_var("BURROW_1", "2");
+
+    let burrow = Burrow::from_env().unwrap();
+
+    assert_eq!(burrow, Burrow("Tri-State Area".to_owned(), Some(2)));
+}
+
+#[test]
+fn test_apply_env() {
+    env::set_var("AGENT_HANDLER", "Monogram");
+    env::remove_var("AGENT_CODE");
+
+    let agent = Agent::builder(AgentParams { code: 7 })
+        .apply_env()
+        .unwrap()
+        .build();
+
+    assert_eq!(agent, Agent {
+        code: 7,
+        handler: Some("Monogram".to_owned())
+    });
+}
+
+#[test]
+fn test_env_errors() {
+    env::remove_var("LAIR_TRAPS");
+    let missing = Lair::from_env().unwrap_err();
+    assert_eq!(missing, LairEnvError::Missing { variable: "LAIR_TRAPS" });
+    assert_eq!(missing.to_string(), "environment variable `LAIR_TRAPS` is not set");
+
+    env::set_var("LAIR_TRAPS", "many");
+    let invalid = Lair::from_env().unwrap_err();
+    assert_eq!(
+        invalid.to_string(),
+        "environment variable `LAIR_TRAPS` has an invalid value `many`: invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_field_without_env() {
+    env::set_var("GADGET_NAME", "Shrinkinator");
+    env::set_var("GADGET_PARTS", "laser");
+
+    let gadget = Gadget::from_env().unwrap();
+    let rebuilt = Gadget::builder(GadgetParams { name: "Inator".to_owned() })
+        .with_parts(Some(vec!["lens".to_owned()]))
+        .apply_env()
+        .unwrap()
+        .build();
+
+    assert_eq!(gadget, Gadget { name: "Shrinkinator".to_owned(), parts: None });
+    assert_eq!(rebuilt, Gadget { name: "Shrinkinator".to_owned(), parts: Some(vec!["lens".to_owned()]) });
+}
@@ -0,0 +1,77 @@
+use struct_builder::builder;
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct AuditInfo {
+    pub created_by: String,
+    pub note: Option<String>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Paging<T> {
+    pub page_size: T,
+    pub page: Option<u32>,
+    #[builder(skip)]
+    pub total: Option<u64>,
+    #[builder(setter(skip))]
+    pub cursor: Option<String>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct CreateOrder {
+    pub customer_id: u64,
+    #[builder(flatten(created_by: String, note: Option<String>))]
+    pub audit: AuditInfo,
+    pub comment: Option<String>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct ListOrders<T> {
+    #[builder(flatten(page_size: T, page: Option<u32>))]
+    pub paging: Paging<T>
+}
+
+#[test]
+fn test_flattened_params() {
+    let order = CreateOrder::builder(CreateOrderParams {
+        customer_id: 7,
+        created_by: "Perry".to_owned()
+    })
+        .with_note(Some("Urgent".to_owned()))
+        .build();
+
+    assert_eq!(order, CreateOrder {
+        customer_id: 7,
+        audit: AuditInfo {
+            created_by: "Perry".to_owned(),
+            note: Some("Urgent".to_owned())
+        },
+        comment: None
+    });
+}
+
+#[test]
+fn test_flattened_setters() {
+    let order: CreateOrder = CreateOrderParams {
+        customer_id: 7,
+        created_by: "Perry".to_owned()
+    }.into();
+
+    let order = CreateOrderBuilder::from(order)
+        .with_created_by("Monogram".to_owned())
+        .build();
+
+    assert_eq!(order.audit.created_by, "Monogram");
+}
+
+#[test]
+fn test_generic_flattened_params() {
+    let orders = ListOrders::builder(ListOrdersParams { page_size: 25u8 })
+        .with_page(Some(2))
+        .build();
+
+    assert_eq!(orders.paging, Paging { page_size: 25, page: Some(2), total: None, cursor: None });
+}
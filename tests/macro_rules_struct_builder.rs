@@ -0,0 +1,49 @@
+use struct_builder::builder;
+
+macro_rules! define_user {
+    ($name:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        #[builder(patch, set_by_name)]
+        #[derive(Debug, PartialEq)]
+        pub struct $name {
+            $(pub $field: $ty),*
+        }
+    };
+}
+
+macro_rules! define_account {
+    () => {
+        #[builder(patch, env(prefix = "MACRO_ACCOUNT_"), preset(name = "admin", role = "admin".to_owned()))]
+        #[derive(Debug, PartialEq)]
+        pub struct Account {
+            pub id: u64,
+            pub role: Option<String>,
+            #[builder(skip)]
+            pub cached: u8
+        }
+
+        #[builder]
+        #[derive(Debug, PartialEq)]
+        pub struct Pair(pub u8, pub Option<u8>);
+    };
+}
+
+define_user!(User { email: String, age: Option<u8> });
+define_account!();
+
+#[test]
+fn test_struct_from_macro_arguments() {
+    let mut builder = User::builder(UserParams { email: "a@b.c".to_owned() }).with_age(Some(3));
+    builder.set_by_name("age", "4").unwrap();
+    let user = builder.apply(UserPatch { email: None, age: Some(Some(5)) }).build();
+
+    assert_eq!(user, User { email: "a@b.c".to_owned(), age: Some(5) });
+}
+
+#[test]
+fn test_struct_from_macro_body() {
+    let account = AccountBuilder::admin(AccountParams { id: 1 }).build();
+    let pair = Pair::builder(PairParams(1)).with_1(Some(2)).build();
+
+    assert_eq!(account, Account { id: 1, role: Some("admin".to_owned()), cached: 0 });
+    assert_eq!(pair, Pair(1, Some(2)));
+}
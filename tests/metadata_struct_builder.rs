@@ -0,0 +1,72 @@
+use struct_builder::builder;
+
+#[builder]
+pub struct Platypus<T> {
+    /// The platypus' name.
+    pub name: String,
+    /// How many missions the platypus has been on.
+    pub missions: Option<u32>,
+    pub gadget: T,
+    #[builder(skip)]
+    pub cached: u64
+}
+
+#[builder]
+pub struct Burrow(pub String, #[builder(name = "depth")] pub Option<u8>);
+
+#[test]
+fn test_fields() {
+    assert_eq!(Platypus::<bool>::FIELDS, &[
+        PlatypusFieldInfo {
+            name: "name",
+            ty: "String",
+            required: true,
+            generic: false,
+            skipped: false,
+            doc: "The platypus' name."
+        },
+        PlatypusFieldInfo {
+            name: "missions",
+            ty: "Option<u32>",
+            required: false,
+            generic: false,
+            skipped: false,
+            doc: "How many missions the platypus has been on."
+        },
+        PlatypusFieldInfo {
+            name: "gadget",
+            ty: "T",
+            required: true,
+            generic: true,
+            skipped: false,
+            doc: ""
+        },
+        PlatypusFieldInfo {
+            name: "cached",
+            ty: "u64",
+            required: false,
+            generic: false,
+            skipped: true,
+            doc: ""
+        }
+    ]);
+    assert_eq!(PlatypusParams::<bool>::FIELDS, Platypus::<bool>::FIELDS);
+    assert_eq!(PlatypusBuilder::<bool>::FIELDS, Platypus::<bool>::FIELDS);
+}
+
+#[test]
+fn test_field_counts() {
+    assert_eq!(Platypus::<bool>::REQUIRED_FIELD_COUNT, 1);
+    assert_eq!(Platypus::<bool>::OPTIONAL_FIELD_COUNT, 1);
+    assert_eq!(Platypus::<bool>::GENERIC_REQUIRED_FIELD_COUNT, 1);
+    assert_eq!(Platypus::<bool>::GENERIC_OPTIONAL_FIELD_COUNT, 0);
+    assert_eq!(PlatypusBuilder::<bool>::SKIPPED_FIELD_COUNT, 1);
+}
+
+#[test]
+fn test_unnamed_fields() {
+    let names = BurrowParams::FIELDS.iter().map(|field| field.name).collect::<Vec<_>>();
+
+    assert_eq!(names, vec!["0", "depth"]);
+    assert_eq!(Burrow::REQUIRED_FIELD_COUNT, 1);
+}
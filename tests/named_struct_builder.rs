@@ -97,3 +97,26 @@ fn test_params_has_attributes() {
     // Test PartialEq
     assert_eq!(params, new_params);
 }
+
+#[test]
+fn test_builder_update_fns() {
+    let subject = Platypus {
+        age: 2,
+        color: (1, 2, 3),
+        name: Some("perry"),
+        is_perry: false
+    };
+
+    let platypus = PlatypusBuilder::from(subject)
+        .map_age(|age| age + 1)
+        .with_is_perry_if(true, true)
+        .with_color_if(false, (0, 0, 0))
+        .maybe_with_name(None)
+        .modify(|platypus| platypus.color.0 = 36)
+        .build();
+
+    assert_eq!(platypus.age, 3);
+    assert_eq!(platypus.color, (36, 2, 3));
+    assert_eq!(platypus.name, Some("perry"));
+    assert!(platypus.is_perry);
+}
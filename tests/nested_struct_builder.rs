@@ -0,0 +1,83 @@
+use struct_builder::builder;
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Address {
+    pub line1: String,
+    pub line2: Option<String>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Agency {
+    pub name: String,
+    #[builder(nested)]
+    pub headquarters: Address
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Platypus {
+    pub name: String,
+    #[builder(nested)]
+    pub agency: Agency,
+    #[builder(nested)]
+    pub home: Option<Address>
+}
+
+#[test]
+fn test_with_nested_builder() {
+    let platypus = Platypus::builder(PlatypusParams {
+        name: "Perry".to_owned(),
+        agency: Agency::builder(AgencyParams {
+            name: "O.W.C.A.".to_owned(),
+            headquarters: Address::builder(AddressParams { line1: "Unknown".to_owned() }).build()
+        }).build()
+    })
+        .with_home_builder(AddressParams { line1: "Maple Drive".to_owned() }, |address| {
+            address.with_line2(Some("Danville".to_owned()))
+        })
+        .update_agency(|agency| {
+            agency.with_headquarters_builder(AddressParams { line1: "Secret Lair".to_owned() }, |address| address)
+        })
+        .build();
+
+    assert_eq!(platypus, Platypus {
+        name: "Perry".to_owned(),
+        agency: Agency {
+            name: "O.W.C.A.".to_owned(),
+            headquarters: Address {
+                line1: "Secret Lair".to_owned(),
+                line2: None
+            }
+        },
+        home: Some(Address {
+            line1: "Maple Drive".to_owned(),
+            line2: Some("Danville".to_owned())
+        })
+    });
+}
+
+#[test]
+fn test_update_optional_nested() {
+    let agency = Agency::builder(AgencyParams {
+        name: "O.W.C.A.".to_owned(),
+        headquarters: Address::builder(AddressParams { line1: "Unknown".to_owned() }).build()
+    }).build();
+
+    let homeless = Platypus::builder(PlatypusParams { name: "Perry".to_owned(), agency })
+        .update_home(|address| address.with_line2(Some("Danville".to_owned())))
+        .build();
+
+    assert_eq!(homeless.home, None);
+
+    let platypus = Platypus::builder(PlatypusParams { name: "Perry".to_owned(), agency: homeless.agency })
+        .with_home(Some(Address::builder(AddressParams { line1: "Maple Drive".to_owned() }).build()))
+        .update_home(|address| address.with_line2(Some("Danville".to_owned())))
+        .build();
+
+    assert_eq!(platypus.home, Some(Address {
+        line1: "Maple Drive".to_owned(),
+        line2: Some("Danville".to_owned())
+    }));
+}
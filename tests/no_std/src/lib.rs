@@ -0,0 +1,57 @@
+//! Builder structs in a `no_std` crate that allocates, for which the generated code can only use `core` and `alloc`.
+//! The tests use them from another crate, which is what `#[non_exhaustive]` structs are restricted in.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::NonZeroU16;
+use struct_builder::builder;
+
+#[builder(patch, set_by_name, preset(name = "fast", timeout = Some(10)))]
+#[derive(Debug, PartialEq)]
+pub struct Sensor {
+    pub id: u8,
+    #[builder(set_by_name = false)]
+    pub label: &'static str,
+    pub timeout: Option<u16>,
+    #[builder(skip)]
+    pub readings: u32
+}
+
+#[builder(patch, set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Reading(pub u16, pub Option<i8>);
+
+#[builder(patch)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Buffer<'a, T: Clone, const N: usize> {
+    pub name: &'a str,
+    pub values: Vec<T>,
+    pub window: [T; N],
+    pub note: Option<String>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Device {
+    #[builder(params_type = u16, try_convert)]
+    pub address: NonZeroU16,
+    #[builder(nested)]
+    pub sensor: Option<Sensor>
+}
+
+#[builder(patch, profile(name = "zeroed", required(offset)))]
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+pub struct Calibration {
+    pub offset: i16,
+    pub scale: Option<u8>
+}
+
+#[builder]
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+pub struct Threshold(pub u16, pub Option<u16>);
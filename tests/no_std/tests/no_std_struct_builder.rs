@@ -0,0 +1,84 @@
+use struct_builder_no_std::{Buffer, BufferParams, BufferPatch, Calibration, CalibrationParams, CalibrationPatch, CalibrationZeroedParams, Device, DeviceParams, Reading, ReadingParams, Sensor, SensorBuilder, SensorParams, Threshold, ThresholdParams};
+
+#[test]
+fn test_set_by_name() {
+    let mut builder = Sensor::builder(SensorParams { id: 1, label: "temperature" });
+    builder.set_by_name("timeout", "30").unwrap();
+
+    let error = builder.set_by_name("timout", "30").unwrap_err();
+
+    assert_eq!(builder.build(), Sensor { id: 1, label: "temperature", timeout: Some(30), readings: 0 });
+    assert_eq!(error.to_string(), "unknown field `timout`, did you mean `timeout`?");
+}
+
+#[test]
+fn test_set_by_name_of_unnamed_fields() {
+    let mut builder = Reading::builder(ReadingParams(512));
+    builder.set_by_name("1", "-3").unwrap();
+
+    assert_eq!(builder.build(), Reading(512, Some(-3)));
+}
+
+#[test]
+fn test_preset() {
+    let sensor = SensorBuilder::fast(SensorParams { id: 2, label: "pressure" }).build();
+
+    assert_eq!(sensor.timeout, Some(10));
+}
+
+#[test]
+fn test_unnamed_patch() {
+    let reading = Reading::builder(ReadingParams(512))
+        .map_0(|value| value * 2)
+        .with_1(Some(-3))
+        .build();
+
+    assert_eq!(reading, Reading(1024, Some(-3)));
+    assert_eq!(Reading::diff(&Reading(1024, None), &reading).1, Some(Some(-3)));
+}
+
+#[test]
+fn test_generic_fields() {
+    let name = String::from("samples");
+    let mut buffer = Buffer::builder(BufferParams { name: &name, values: vec![1u8, 2, 3], window: [0; 2] })
+        .with_note(Some("raw".to_owned()))
+        .build();
+    buffer.apply(BufferPatch { values: Some(vec![4]), ..BufferPatch::empty() });
+
+    assert_eq!(buffer, Buffer { name: "samples", values: vec![4], window: [0, 0], note: Some("raw".to_owned()) });
+    assert_eq!(Buffer::<u8, 2>::GENERIC_REQUIRED_FIELD_COUNT, 3);
+}
+
+#[test]
+fn test_try_convert() {
+    let device = Device::builder(DeviceParams { address: 0x40 })
+        .unwrap()
+        .with_sensor_builder(SensorParams { id: 3, label: "light" }, |sensor| sensor)
+        .build();
+    let error = Device::builder(DeviceParams { address: 0 }).err().unwrap();
+
+    assert_eq!(device.address.get(), 0x40);
+    assert_eq!(device.sensor.map(|sensor| sensor.id), Some(3));
+    assert_eq!(error.field, "address");
+}
+
+#[test]
+fn test_non_exhaustive_params_from_other_crate() {
+    let mut calibration = Calibration::builder(CalibrationParams::new(-4))
+        .with_scale(Some(2))
+        .build();
+    calibration.apply(CalibrationPatch { offset: Some(5), ..CalibrationPatch::empty() });
+    let zeroed = Calibration::zeroed_builder(CalibrationZeroedParams::new(0)).build();
+
+    assert_eq!((calibration.offset, calibration.scale), (5, Some(2)));
+    assert_eq!((zeroed.offset, zeroed.scale), (0, None));
+}
+
+#[test]
+fn test_non_exhaustive_unnamed_params_from_other_crate() {
+    let threshold = Threshold::builder(ThresholdParams::new(100))
+        .with_1(Some(120))
+        .build();
+
+    assert_eq!((threshold.0, threshold.1), (100, Some(120)));
+}
@@ -0,0 +1,38 @@
+use struct_builder::builder;
+
+#[builder(patch, profile(name = "named", required(id, name)))]
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+pub struct Account {
+    pub id: u64,
+    pub name: Option<String>,
+    pub active: Option<bool>
+}
+
+#[builder]
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+pub struct Point(pub i32, pub i32, pub Option<String>);
+
+#[test]
+fn test_params_constructor() {
+    let account = Account::builder(AccountParams::new(7))
+        .with_active(Some(true))
+        .build();
+
+    assert_eq!(account, Account { id: 7, name: None, active: Some(true) });
+}
+
+#[test]
+fn test_profile_params_constructor() {
+    let account = Account::named_builder(AccountNamedParams::new(7, "Perry".to_owned())).build();
+
+    assert_eq!(account.name, Some("Perry".to_owned()));
+}
+
+#[test]
+fn test_unnamed_params_constructor() {
+    let point = Point::from(PointParams::new(1, 2));
+
+    assert_eq!(point, Point(1, 2, None));
+}
@@ -0,0 +1,53 @@
+mod users {
+    use struct_builder::builder;
+
+    #[builder]
+    #[derive(Debug, PartialEq)]
+    pub struct User {
+        id: u64,
+        name: String,
+        nickname: Option<String>
+    }
+
+    impl User {
+        pub fn id(&self) -> u64 {
+            self.id
+        }
+    }
+
+    #[builder(params_field_vis = "pub(crate)")]
+    pub struct Session {
+        token: String,
+        #[builder(params_field_vis = "pub(super)")]
+        user_id: u64
+    }
+
+    impl Session {
+        pub fn token(&self) -> &str {
+            &self.token
+        }
+
+        pub fn user_id(&self) -> u64 {
+            self.user_id
+        }
+    }
+}
+
+use users::{Session, SessionParams, User, UserParams};
+
+#[test]
+fn test_private_fields_are_public_in_params() {
+    let user = User::from(UserParams { id: 7, name: "Perry".to_owned() });
+
+    assert_eq!(user.id(), 7);
+}
+
+#[test]
+fn test_params_field_vis() {
+    let params = SessionParams { token: "platypus".to_owned(), user_id: 7 };
+
+    let session = Session::from(params);
+
+    assert_eq!(session.token(), "platypus");
+    assert_eq!(session.user_id(), 7);
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use struct_builder::builder;
+
+#[builder(patch)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Platypus {
+    pub name: String,
+    pub age: u8,
+    pub nickname: Option<String>,
+    #[builder(skip)]
+    pub sightings: u32
+}
+
+#[builder(patch)]
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Lair {
+    #[serde(rename = "trapCount", with = "as_string")]
+    pub traps: u8
+}
+
+mod as_string {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[builder(patch)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Burrow(pub String, pub Option<u8>);
+
+fn perry() -> Platypus {
+    Platypus::builder(PlatypusParams { name: "Perry".to_owned(), age: 3 })
+        .with_nickname(Some("Agent P".to_owned()))
+        .build()
+}
+
+#[test]
+fn test_subject_apply_patch() {
+    let mut platypus = perry();
+
+    platypus.apply(PlatypusPatch {
+        age: Some(4),
+        nickname: Some(None),
+        ..PlatypusPatch::empty()
+    });
+
+    assert_eq!(platypus.name, "Perry".to_owned());
+    assert_eq!(platypus.age, 4);
+    assert_eq!(platypus.nickname, None);
+}
+
+#[test]
+fn test_builder_apply_patch() {
+    let patch = PlatypusPatch {
+        name: Some("Peter".to_owned()),
+        ..PlatypusPatch::empty()
+    };
+
+    let platypus = PlatypusBuilder::from(perry())
+        .apply(patch)
+        .with_age(5)
+        .build();
+
+    assert_eq!(platypus.name, "Peter".to_owned());
+    assert_eq!(platypus.age, 5);
+    assert_eq!(platypus.nickname, Some("Agent P".to_owned()));
+}
+
+#[test]
+fn test_patch_merge() {
+    let base = PlatypusPatch {
+        name: Some("Peter".to_owned()),
+        age: Some(4),
+        ..PlatypusPatch::empty()
+    };
+    let overlay = PlatypusPatch {
+        age: Some(5),
+        nickname: Some(None),
+        ..PlatypusPatch::empty()
+    };
+
+    let merged = base.merge(overlay);
+
+    assert_eq!(merged.name, Some("Peter".to_owned()));
+    assert_eq!(merged.age, Some(5));
+    assert_eq!(merged.nickname, Some(None));
+}
+
+#[test]
+fn test_subject_diff() {
+    let from = perry();
+    let mut to = perry();
+    to.age = 4;
+    to.nickname = None;
+
+    let patch = Platypus::diff(&from, &to);
+
+    assert_eq!(patch.name, None);
+    assert_eq!(patch.age, Some(4));
+    assert_eq!(patch.nickname, Some(None));
+
+    let mut patched = from.clone();
+    patched.apply(patch);
+    assert_eq!(patched, to);
+}
+
+#[test]
+fn test_patch_from_json() {
+    let patch: PlatypusPatch = serde_json::from_str(r#"{ "age": 6 }"#).unwrap();
+
+    let mut platypus = perry();
+    platypus.apply(patch);
+
+    assert_eq!(platypus.name, "Perry".to_owned());
+    assert_eq!(platypus.age, 6);
+    assert_eq!(platypus.nickname, Some("Agent P".to_owned()));
+}
+
+#[test]
+fn test_patch_from_json_with_null() {
+    let patch: PlatypusPatch = serde_json::from_str(r#"{ "nickname": null }"#).unwrap();
+
+    assert_eq!(patch.nickname, Some(None));
+
+    let mut platypus = perry();
+    platypus.apply(patch);
+
+    assert_eq!(platypus.nickname, None);
+}
+
+#[test]
+fn test_patch_json_round_trip() {
+    let from = perry();
+    let mut to = from.clone();
+    to.age = 5;
+    to.nickname = None;
+
+    let json = serde_json::to_string(&Platypus::diff(&from, &to)).unwrap();
+    let patch: PlatypusPatch = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, r#"{"age":5,"nickname":null}"#);
+    assert_eq!(patch.nickname, Some(None));
+}
+
+#[test]
+fn test_patch_without_type_dependent_serde_attributes() {
+    let lair: Lair = serde_json::from_str(r#"{ "trapCount": "3" }"#).unwrap();
+    let patch: LairPatch = serde_json::from_str(r#"{ "trapCount": 7 }"#).unwrap();
+
+    assert_eq!(lair.traps, 3);
+    assert_eq!(patch.traps, Some(7));
+}
+
+#[test]
+fn test_unnamed_patch() {
+    let mut burrow = Burrow::builder(BurrowParams("Tri-State Area".to_owned())).build();
+
+    burrow.apply(BurrowPatch(None, Some(Some(2))));
+
+    assert_eq!(burrow, Burrow("Tri-State Area".to_owned(), Some(2)));
+    assert_eq!(Burrow::diff(&burrow, &burrow), BurrowPatch(None, None));
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub lat: i32,
+    pub lon: i32
+}
+
+#[builder(patch)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hideout {
+    pub name: String,
+    #[serde(flatten)]
+    pub location: Coordinates
+}
+
+#[test]
+fn test_patch_flattened_field() {
+    let renamed: HideoutPatch = serde_json::from_str(r#"{ "name": "Lair" }"#).unwrap();
+    let moved: HideoutPatch = serde_json::from_str(r#"{ "lat": 1, "lon": 2 }"#).unwrap();
+
+    assert_eq!(renamed, HideoutPatch { name: Some("Lair".to_owned()), location: None });
+    assert_eq!(moved, HideoutPatch { name: None, location: Some(Coordinates { lat: 1, lon: 2 }) });
+    assert_eq!(serde_json::to_string(&moved).unwrap(), r#"{"lat":1,"lon":2}"#);
+}
@@ -0,0 +1,48 @@
+use struct_builder::builder;
+
+#[builder(
+    preset(name = "local_dev", age = 30, first_name = Some("Dev".into()), retries = 5),
+    preset(name = "anonymous", first_name = None)
+)]
+#[derive(Debug, PartialEq)]
+pub struct Person {
+    pub email: String,
+    pub age: Option<u8>,
+    pub first_name: Option<String>,
+    #[builder(skip = 3)]
+    pub retries: u8
+}
+
+#[test]
+fn test_preset() {
+    let person = PersonBuilder::local_dev(PersonParams { email: "dev@owca.org".to_owned() }).build();
+
+    assert_eq!(person, Person {
+        email: "dev@owca.org".to_owned(),
+        age: Some(30),
+        first_name: Some("Dev".to_owned()),
+        retries: 5
+    });
+}
+
+#[test]
+fn test_preset_with_setters() {
+    let person = PersonBuilder::local_dev(PersonParams { email: "dev@owca.org".to_owned() })
+        .with_age(Some(31))
+        .build();
+
+    assert_eq!(person.age, Some(31));
+    assert_eq!(person.first_name, Some("Dev".to_owned()));
+}
+
+#[test]
+fn test_preset_with_none() {
+    let person = PersonBuilder::anonymous(PersonParams { email: "anon@owca.org".to_owned() }).build();
+
+    assert_eq!(person, Person {
+        email: "anon@owca.org".to_owned(),
+        age: None,
+        first_name: None,
+        retries: 3
+    });
+}
@@ -0,0 +1,60 @@
+use struct_builder::builder;
+
+#[derive(Debug, PartialEq)]
+pub enum Role {
+    Admin,
+    Agent
+}
+
+#[builder(
+    profile(name = "admin", required(email, role)),
+    profile(name = "self_signup", required(email, invite_code))
+)]
+#[derive(Debug, PartialEq)]
+pub struct User {
+    pub email: String,
+    pub role: Option<Role>,
+    pub invite_code: Option<String>,
+    pub display_name: Option<String>
+}
+
+#[test]
+fn test_admin_profile() {
+    let user = User::admin_builder(UserAdminParams {
+        email: "perry@owca.org".to_owned(),
+        role: Role::Agent
+    })
+        .with_display_name(Some("Agent P".to_owned()))
+        .build();
+
+    assert_eq!(user, User {
+        email: "perry@owca.org".to_owned(),
+        role: Some(Role::Agent),
+        invite_code: None,
+        display_name: Some("Agent P".to_owned())
+    });
+}
+
+#[test]
+fn test_self_signup_profile() {
+    let user = User::self_signup_builder(UserSelfSignupParams {
+        email: "perry@owca.org".to_owned(),
+        invite_code: "PLATYPUS".to_owned()
+    }).build();
+
+    assert_eq!(user, User {
+        email: "perry@owca.org".to_owned(),
+        role: None,
+        invite_code: Some("PLATYPUS".to_owned()),
+        display_name: None
+    });
+}
+
+#[test]
+fn test_default_builder() {
+    let user = User::builder(UserParams { email: "perry@owca.org".to_owned() })
+        .with_role(Some(Role::Admin))
+        .build();
+
+    assert_eq!(user.role, Some(Role::Admin));
+}
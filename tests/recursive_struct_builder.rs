@@ -0,0 +1,59 @@
+use struct_builder::builder;
+
+#[builder(patch)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tree<T> {
+    pub value: T,
+    pub children: Vec<Self>,
+    pub parent_value: Option<T>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Node {
+    pub value: u8,
+    pub next: Option<Box<Self>>
+}
+
+#[builder]
+#[derive(Debug, PartialEq)]
+pub struct Link(u8, Box<Option<Self>>);
+
+#[test]
+fn test_tree() {
+    let leaf = Tree::builder(TreeParams { value: 2, children: vec![] })
+        .with_parent_value(Some(1))
+        .build();
+    let tree = Tree::builder(TreeParams { value: 1, children: vec![leaf.clone()] }).build();
+
+    assert_eq!(tree.children, vec![leaf]);
+}
+
+#[test]
+fn test_tree_patch() {
+    let mut tree = Tree::from(TreeParams { value: 1, children: vec![] });
+    let child = Tree::from(TreeParams { value: 2, children: vec![] });
+    tree.apply(TreePatch { children: Some(vec![child.clone()]), ..TreePatch::empty() });
+
+    assert_eq!(tree.children, vec![child]);
+}
+
+#[test]
+fn test_linked_list() {
+    let tail = Node::from(NodeParams { value: 2 });
+    let head = Node::builder(NodeParams { value: 1 })
+        .with_next(Some(Box::new(tail)))
+        .build();
+
+    assert_eq!(head.next.map(|next| next.value), Some(2));
+}
+
+#[test]
+fn test_unnamed_linked_list() {
+    let tail = Link::from(LinkParams(2, Box::new(None)));
+    let head = Link::builder(LinkParams(1, Box::new(None)))
+        .with_1(Box::new(Some(tail)))
+        .build();
+
+    assert_eq!(head.1.map(|next| next.0), Some(2));
+}
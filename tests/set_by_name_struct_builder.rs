@@ -0,0 +1,105 @@
+use struct_builder::builder;
+
+#[builder(set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Platypus {
+    pub name: String,
+    pub age: u8,
+    pub first_name: Option<String>
+}
+
+#[builder(set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Lair {
+    pub name: String,
+    #[builder(set_by_name = false)]
+    pub traps: Vec<String>
+}
+
+#[builder(set_by_name)]
+#[derive(Debug, PartialEq)]
+pub struct Burrow(pub String, #[builder(name = "depth")] pub Option<u8>);
+
+#[test]
+fn test_set_by_name() {
+    let mut builder = Platypus::builder(PlatypusParams {
+        name: "Perry".to_owned(),
+        age: 3
+    });
+
+    builder.set_by_name("age", "35").unwrap();
+    builder.set_by_name("first_name", "John").unwrap();
+
+    assert_eq!(builder.build(), Platypus {
+        name: "Perry".to_owned(),
+        age: 35,
+        first_name: Some("John".to_owned())
+    });
+}
+
+#[test]
+fn test_apply_pairs() {
+    let mut builder = Platypus::builder(PlatypusParams {
+        name: "Perry".to_owned(),
+        age: 3
+    });
+
+    builder.apply_pairs([("name", "Agent P"), ("age", "4")]).unwrap();
+
+    assert_eq!(builder.build(), Platypus {
+        name: "Agent P".to_owned(),
+        age: 4,
+        first_name: None
+    });
+}
+
+#[test]
+fn test_set_unknown_field() {
+    let mut builder = Platypus::builder(PlatypusParams {
+        name: "Perry".to_owned(),
+        age: 3
+    });
+
+    let suggested = builder.set_by_name("firstname", "John").unwrap_err();
+    let unknown = builder.set_by_name("owner", "Phineas").unwrap_err();
+
+    assert_eq!(suggested, PlatypusSetFieldError::UnknownField {
+        field: "firstname".to_owned(),
+        suggestion: Some("first_name")
+    });
+    assert_eq!(suggested.to_string(), "unknown field `firstname`, did you mean `first_name`?");
+    assert_eq!(unknown.to_string(), "unknown field `owner`");
+}
+
+#[test]
+fn test_set_invalid_value() {
+    let mut builder = Platypus::builder(PlatypusParams {
+        name: "Perry".to_owned(),
+        age: 3
+    });
+
+    let error = builder.set_by_name("age", "old").unwrap_err();
+
+    assert_eq!(error.to_string(), "invalid value `old` for field `age`: invalid digit found in string");
+}
+
+#[test]
+fn test_unnamed_set_by_name() {
+    let mut builder = Burrow::builder(BurrowParams("Danville".to_owned()));
+
+    builder.apply_pairs([("0", "Tri-State Area"), ("depth", "2")]).unwrap();
+
+    assert_eq!(builder.build(), Burrow("Tri-State Area".to_owned(), Some(2)));
+}
+
+#[test]
+fn test_field_without_set_by_name() {
+    let mut builder = Lair::builder(LairParams { name: "Doofenshmirtz Evil Inc.".to_owned(), traps: vec![] })
+        .with_traps(vec!["cage".to_owned()]);
+
+    builder.set_by_name("name", "Evil Lair").unwrap();
+    let error = builder.set_by_name("traps", "net").unwrap_err();
+
+    assert_eq!(error.to_string(), "unknown field `traps`");
+    assert_eq!(builder.build(), Lair { name: "Evil Lair".to_owned(), traps: vec!["cage".to_owned()] });
+}
@@ -0,0 +1,48 @@
+use struct_builder::builder;
+
+mod tenancy {
+    use struct_builder::builder;
+
+    #[builder]
+    pub struct Account {
+        #[builder(setter(skip))]
+        pub tenant_id: u64,
+        #[builder(setter(vis = "pub(crate)"))]
+        pub plan: Option<String>,
+        pub name: String
+    }
+}
+
+#[builder]
+pub struct Quota(
+    #[builder(setter(skip))]
+    pub u64,
+    pub Option<u64>
+);
+
+#[test]
+fn test_params_only_field_is_set_from_params() {
+    let params = tenancy::AccountParams {
+        tenant_id: 7,
+        name: "Perry".to_owned()
+    };
+
+    let account = tenancy::Account::builder(params)
+        .with_plan(Some("free".to_owned()))
+        .with_name("Agent P".to_owned())
+        .build();
+
+    assert_eq!(account.tenant_id, 7);
+    assert_eq!(account.plan, Some("free".to_owned()));
+    assert_eq!(account.name, "Agent P".to_owned());
+}
+
+#[test]
+fn test_params_only_unnamed_field_is_set_from_params() {
+    let quota = Quota::builder(QuotaParams(10))
+        .with_1(Some(5))
+        .build();
+
+    assert_eq!(quota.0, 10);
+    assert_eq!(quota.1, Some(5));
+}
@@ -0,0 +1,89 @@
+#![allow(dead_code, non_camel_case_types)]
+
+use struct_builder::builder;
+
+// Shadow the prelude, so the generated code has to use fully qualified paths
+struct Option;
+struct Some;
+struct None;
+struct Result;
+struct Ok;
+struct Err;
+struct String;
+struct Vec;
+struct Box;
+trait From {}
+trait Into {}
+trait Default {}
+trait Clone {}
+trait Copy {}
+trait Debug {}
+trait PartialEq {}
+trait Eq {}
+trait ToString {}
+trait Iterator {}
+trait IntoIterator {}
+trait FnOnce {}
+trait Send {}
+trait Sync {}
+trait Sized {}
+
+#[builder(patch, set_by_name, env(prefix = "SHADOWED_"), preset(name = "local", port = 8080))]
+pub struct Server<T: ::core::str::FromStr + ::core::clone::Clone + ::core::cmp::PartialEq = u8> {
+    pub host: ::std::string::String,
+    pub port: ::core::option::Option<u16>,
+    pub tag: T,
+    #[builder(skip)]
+    pub connections: u32
+}
+
+#[builder(patch, set_by_name)]
+pub struct Pair(pub u8, pub ::core::option::Option<bool>);
+
+#[builder]
+pub struct Port {
+    #[builder(params_type = u32, try_convert)]
+    pub number: u16
+}
+
+#[builder]
+pub struct Label {
+    #[builder(params_type = &'static str)]
+    pub text: ::std::string::String
+}
+
+#[builder]
+pub struct Parent {
+    #[builder(nested)]
+    pub child: Label
+}
+
+#[test]
+fn test_named_fields() {
+    let mut builder = Server::builder(ServerParams { host: "localhost".into(), tag: 1 })
+        .with_port(::core::option::Option::Some(80));
+    builder.set_by_name("tag", "2").unwrap();
+
+    let mut server = builder.build();
+    server.apply(ServerPatch { host: ::core::option::Option::None, port: ::core::option::Option::Some(::core::option::Option::None), tag: ::core::option::Option::None });
+
+    assert_eq!(server.port, ::core::option::Option::None);
+    assert_eq!(server.tag, 2);
+    assert_eq!(ServerBuilder::local(ServerParams { host: "localhost".into(), tag: 1 }).build().port, ::core::option::Option::Some(8080));
+}
+
+#[test]
+fn test_unnamed_fields() {
+    let mut builder = Pair::builder(PairParams(1));
+    builder.set_by_name("1", "true").unwrap();
+
+    let pair = builder.build();
+
+    assert_eq!(pair.1, ::core::option::Option::Some(true));
+}
+
+#[test]
+fn test_conversions() {
+    assert!(Port::builder(PortParams { number: 80 }).is_ok());
+    assert_eq!(Parent::builder(ParentParams { child: Label::builder(LabelParams { text: "child" }).build() }).build().child.text, "child");
+}
@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+use struct_builder::builder;
+
+#[builder]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Platypus<T> {
+    pub name: String,
+    pub nickname: Option<String>,
+    #[builder(skip)]
+    pub sightings: u32,
+    #[builder(skip = String::from("platypus"))]
+    pub species: String,
+    pub marker: PhantomData<T>
+}
+
+#[builder]
+pub struct Burrow(
+    pub String,
+    #[builder(skip = 2)]
+    pub u8,
+    pub Option<u8>
+);
+
+#[test]
+fn test_skipped_fields_are_initialized() {
+    let params = PlatypusParams { name: "Perry".to_owned() };
+
+    let platypus: Platypus<u8> = Platypus::builder(params)
+        .with_nickname(Some("Agent P".to_owned()))
+        .build();
+
+    assert_eq!(platypus.name, "Perry".to_owned());
+    assert_eq!(platypus.nickname, Some("Agent P".to_owned()));
+    assert_eq!(platypus.sightings, 0);
+    assert_eq!(platypus.species, "platypus".to_owned());
+    assert_eq!(platypus.marker, PhantomData);
+}
+
+#[test]
+fn test_skipped_unnamed_fields_are_initialized() {
+    let params = BurrowParams("Tri-State Area".to_owned());
+
+    let burrow = Burrow::builder(params)
+        .with_2(Some(1))
+        .build();
+
+    assert_eq!(burrow.0, "Tri-State Area".to_owned());
+    assert_eq!(burrow.1, 2);
+    assert_eq!(burrow.2, Some(1));
+}
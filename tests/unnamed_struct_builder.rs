@@ -22,4 +22,19 @@ fn test_unnamed_struct_builder() {
     assert_eq!(platypus.1, (36, 167, 161));
     assert_eq!(platypus.2, Some(String::from("Perry")));
     assert_eq!(platypus.3, false);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_unnamed_struct_builder_update_fns() {
+    let params = PlatypusParams(3, (36, 167, 161), true);
+
+    let platypus = Platypus::builder(params)
+        .map_0(|age| age * 2)
+        .maybe_with_2(Some(Some(String::from("Perry"))))
+        .with_3_if(false, false)
+        .build();
+
+    assert_eq!(platypus.0, 6);
+    assert_eq!(platypus.2, Some(String::from("Perry")));
+    assert!(platypus.3);
+}